@@ -0,0 +1,406 @@
+#![deny(missing_docs)]
+
+//! # `typechain-build`
+//!
+//! This crate generates chainlinks and chains from a
+//! JSON schema file in a build script, so the schema
+//! can be shared with tools outside of Rust. The
+//! generated source uses the macros of the
+//! [`typechain`](https://crates.io/crates/typechain)
+//! crate, so it behaves the same as if it had been
+//! written by hand.
+//!
+//! A schema lists chainlinks with their fields, and
+//! chains with the chainlinks they implement:
+//!
+//! ```json
+//! {
+//!   "chainlinks": [
+//!     {
+//!       "name": "Person",
+//!       "fields": [
+//!         { "name": "name", "kind": "const", "type": "String" },
+//!         { "name": "age", "kind": "mut", "type": "u8" },
+//!         { "name": "species", "kind": "static", "type": "&'static str" }
+//!       ]
+//!     }
+//!   ],
+//!   "chains": [
+//!     {
+//!       "name": "Employee",
+//!       "parents": ["Person"],
+//!       "statics": { "species": "\"human\"" }
+//!     }
+//!   ]
+//! }
+//! ```
+//!
+//! A chain gets a field for every `const` and `mut`
+//! item of its parents, and `static` items take their
+//! value from `statics`, written as a Rust expression.
+//!
+//! The schema is compiled from `build.rs`:
+//!
+//! ```no_run
+//! typechain_build::compile("model.json").unwrap();
+//! ```
+//!
+//! and the generated source is included where the
+//! chains should be defined:
+//!
+//! ```ignore
+//! include!(concat!(env!("OUT_DIR"), "/model.rs"));
+//! ```
+
+use std::collections::BTreeMap;
+use std::fmt;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use quote::ToTokens;
+use serde::Deserialize;
+
+
+/// A schema of chainlinks and chains.
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Schema {
+    /// The chainlinks to generate.
+    #[serde(default)]
+    pub chainlinks: Vec<Chainlink>,
+    /// The chains to generate.
+    #[serde(default)]
+    pub chains: Vec<Chain>
+}
+
+/// A chainlink in a schema.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Chainlink {
+    /// The name of the chainlink.
+    pub name: String,
+    /// The fields of the chainlink.
+    #[serde(default)]
+    pub fields: Vec<Field>
+}
+
+/// A field of a chainlink.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Field {
+    /// The name of the field.
+    pub name: String,
+    /// How the field is accessed.
+    pub kind: FieldKind,
+    /// The Rust type of the field.
+    #[serde(rename = "type")]
+    pub ty: String
+}
+
+/// How a chainlink field is accessed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum FieldKind {
+    /// A field behind `&self`.
+    Const,
+    /// A field behind `&mut self`.
+    Mut,
+    /// A value that is the same for every instance.
+    Static
+}
+
+impl FieldKind {
+    /// The keyword of the kind in the macros.
+    fn keyword(self) -> &'static str {
+        match self {
+            FieldKind::Const => "const",
+            FieldKind::Mut => "mut",
+            FieldKind::Static => "static"
+        }
+    }
+}
+
+/// A chain in a schema.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Chain {
+    /// The name of the chain.
+    pub name: String,
+    /// The chainlinks the chain implements.
+    #[serde(default)]
+    pub parents: Vec<String>,
+    /// The values of `static` items, as Rust
+    /// expressions.
+    #[serde(default)]
+    pub statics: BTreeMap<String, String>
+}
+
+/// An error reading or generating a schema.
+#[derive(Debug)]
+pub enum Error {
+    /// The schema could not be read, or the
+    /// generated source could not be written.
+    Io(std::io::Error),
+    /// The schema is not valid JSON, or does not
+    /// have the expected shape.
+    Json(serde_json::Error),
+    /// The schema describes chains that cannot be
+    /// generated.
+    Schema(String)
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::Io(err) => write!(f, "{}", err),
+            Error::Json(err) => write!(f, "invalid schema: {}", err),
+            Error::Schema(message) => write!(f, "{}", message)
+        }
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Error::Io(err) => Some(err),
+            Error::Json(err) => Some(err),
+            Error::Schema(_) => None
+        }
+    }
+}
+
+impl From<std::io::Error> for Error {
+    fn from(err: std::io::Error) -> Self {
+        Error::Io(err)
+    }
+}
+
+impl From<serde_json::Error> for Error {
+    fn from(err: serde_json::Error) -> Self {
+        Error::Json(err)
+    }
+}
+
+/// Return a schema error.
+macro_rules! bail {
+    ($($args:tt)*) => {
+        return Err(Error::Schema(format!($($args)*)))
+    };
+}
+
+/// Check that a string is a Rust identifier.
+fn check_ident(name: &str, what: &str) -> Result<(), Error> {
+    match syn::parse_str::<syn::Ident>(name) {
+        Ok(_) => Ok(()),
+        Err(_) => bail!("{} `{}` is not a valid identifier", what, name)
+    }
+}
+
+/// Normalize a Rust type, so types written with
+/// different spacing compare equal.
+fn normalize_type(ty: &str, field: &str) -> Result<String, Error> {
+    match syn::parse_str::<syn::Type>(ty) {
+        Ok(ty) => Ok(ty.to_token_stream().to_string()),
+        Err(_) => bail!("type `{}` of field `{}` is not a valid Rust type", ty, field)
+    }
+}
+
+impl Schema {
+    /// Parse a schema from JSON.
+    pub fn parse(source: &str) -> Result<Self, Error> {
+        Ok(serde_json::from_str(source)?)
+    }
+
+    /// Read a schema from a JSON file.
+    pub fn read(path: impl AsRef<Path>) -> Result<Self, Error> {
+        Self::parse(&fs::read_to_string(path)?)
+    }
+
+    /// Get a chainlink by name.
+    fn chainlink(&self, name: &str) -> Option<&Chainlink> {
+        self.chainlinks.iter().find(|c| c.name == name)
+    }
+
+    /// Check that names are unique and valid, and
+    /// that every type parses.
+    fn check(&self) -> Result<(), Error> {
+        let mut names: Vec<&str> = Vec::new();
+
+        for chainlink in &self.chainlinks {
+            check_ident(&chainlink.name, "chainlink")?;
+
+            if names.contains(&chainlink.name.as_str()) {
+                bail!("`{}` is defined more than once", chainlink.name);
+            }
+
+            names.push(&chainlink.name);
+
+            let mut fields: Vec<&str> = Vec::new();
+
+            for field in &chainlink.fields {
+                check_ident(&field.name, "field")?;
+                normalize_type(&field.ty, &field.name)?;
+
+                if fields.contains(&field.name.as_str()) {
+                    bail!("chainlink `{}` has more than one field named `{}`", chainlink.name, field.name);
+                }
+
+                fields.push(&field.name);
+            }
+        }
+
+        for chain in &self.chains {
+            check_ident(&chain.name, "chain")?;
+
+            if names.contains(&chain.name.as_str()) {
+                bail!("`{}` is defined more than once", chain.name);
+            }
+
+            names.push(&chain.name);
+        }
+
+        Ok(())
+    }
+
+    /// Generate the Rust source for the schema.
+    ///
+    /// Chainlinks are generated with `chainlink!` and
+    /// chains with `chain!`, in the order they are
+    /// given in.
+    pub fn to_rust(&self) -> Result<String, Error> {
+        self.check()?;
+
+        let mut source = String::new();
+
+        for chainlink in &self.chainlinks {
+            source.push_str(&format!("::typechain::chainlink!({} => {{\n", chainlink.name));
+
+            for field in &chainlink.fields {
+                source.push_str(&format!("    {} {}: {};\n", field.kind.keyword(), field.name, field.ty.trim()));
+            }
+
+            source.push_str("});\n\n");
+        }
+
+        for chain in &self.chains {
+            source.push_str(&self.chain_source(chain)?);
+        }
+
+        Ok(source)
+    }
+
+    /// Generate the `chain!` invocation for a chain.
+    fn chain_source(&self, chain: &Chain) -> Result<String, Error> {
+        // Fields shared by several parents are merged
+        // into one, which must agree on kind and type.
+        let mut fields: Vec<(&Field, String, Vec<&str>)> = Vec::new();
+
+        for (i, parent) in chain.parents.iter().enumerate() {
+            if chain.parents[..i].contains(parent) {
+                bail!("chain `{}` lists chainlink `{}` more than once", chain.name, parent);
+            }
+
+            let chainlink = match self.chainlink(parent) {
+                Some(chainlink) => chainlink,
+                None => bail!("chain `{}` implements `{}`, which is not a chainlink in the schema", chain.name, parent)
+            };
+
+            for field in &chainlink.fields {
+                let ty = normalize_type(&field.ty, &field.name)?;
+
+                match fields.iter_mut().find(|(f, _, _)| f.name == field.name) {
+                    Some((first, first_ty, parents)) => {
+                        if first.kind != field.kind || *first_ty != ty {
+                            bail!(
+                                "chain `{}` cannot implement field `{}` of both `{}` and `{}`, because it is `{} {}` in one and `{} {}` in the other",
+                                chain.name, field.name, parents[0], parent,
+                                first.kind.keyword(), first.ty.trim(), field.kind.keyword(), field.ty.trim()
+                            );
+                        }
+
+                        parents.push(parent);
+                    },
+                    None => fields.push((field, ty, vec![parent]))
+                }
+            }
+        }
+
+        for name in chain.statics.keys() {
+            if !fields.iter().any(|(f, _, _)| f.name == *name && f.kind == FieldKind::Static) {
+                bail!("chain `{}` sets `{}`, which is not a static item of its chainlinks", chain.name, name);
+            }
+        }
+
+        let mut source = format!("::typechain::chain!({} => {{\n", chain.name);
+
+        for (i, (field, _, parents)) in fields.iter().enumerate() {
+            if i > 0 {
+                source.push('\n');
+            }
+
+            let parents = parents.iter().map(|p| format!("@{}", p)).collect::<Vec<_>>().join(" ");
+
+            source.push_str(&format!("    {}\n", parents));
+
+            match field.kind {
+                FieldKind::Static => {
+                    let value = match chain.statics.get(&field.name) {
+                        Some(value) => value,
+                        None => bail!("chain `{}` does not set static item `{}`", chain.name, field.name)
+                    };
+
+                    if syn::parse_str::<syn::Expr>(value).is_err() {
+                        bail!("value `{}` of `{}` in chain `{}` is not a valid Rust expression", value, field.name, chain.name);
+                    }
+
+                    source.push_str(&format!("    static {}: {} = {};\n", field.name, field.ty.trim(), value.trim()));
+                },
+                kind => source.push_str(&format!("    {} {}: {};\n", kind.keyword(), field.name, field.ty.trim()))
+            }
+        }
+
+        source.push_str("});\n\n");
+
+        Ok(source)
+    }
+}
+
+/// Generate the Rust source for a schema file,
+/// writing it to a file of the same name with an
+/// `.rs` extension in `out_dir`.
+///
+/// Returns the path of the generated file.
+pub fn compile_to(schema: impl AsRef<Path>, out_dir: impl AsRef<Path>) -> Result<PathBuf, Error> {
+    let schema = schema.as_ref();
+    let source = Schema::read(schema)?.to_rust()?;
+
+    let name = match schema.file_stem() {
+        Some(name) => name,
+        None => bail!("schema path `{}` has no file name", schema.display())
+    };
+
+    let out = out_dir.as_ref().join(format!("{}.rs", name.to_string_lossy()));
+
+    let header = format!("// Generated by `typechain-build` from `{}`.\n\n", schema.display());
+
+    fs::write(&out, header + &source)?;
+
+    Ok(out)
+}
+
+/// Generate the Rust source for a schema file from a
+/// build script.
+///
+/// The source is written to `OUT_DIR`, in a file of
+/// the same name with an `.rs` extension, and the
+/// build script is rerun when the schema changes.
+pub fn compile(schema: impl AsRef<Path>) -> Result<PathBuf, Error> {
+    let out_dir = match std::env::var_os("OUT_DIR") {
+        Some(out_dir) => out_dir,
+        None => bail!("`OUT_DIR` is not set, so `compile` must be called from a build script")
+    };
+
+    println!("cargo:rerun-if-changed={}", schema.as_ref().display());
+
+    compile_to(schema, out_dir)
+}
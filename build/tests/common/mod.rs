@@ -0,0 +1,29 @@
+/// A schema with a chain implementing two chainlinks
+/// that share a field.
+pub const SCHEMA: &str = r#"
+{
+  "chainlinks": [
+    {
+      "name": "Person",
+      "fields": [
+        { "name": "name", "kind": "const", "type": "String" },
+        { "name": "age", "kind": "mut", "type": "u8" },
+        { "name": "species", "kind": "static", "type": "&'static str" }
+      ]
+    },
+    {
+      "name": "Named",
+      "fields": [
+        { "name": "name", "kind": "const", "type": "String" }
+      ]
+    }
+  ],
+  "chains": [
+    {
+      "name": "Employee",
+      "parents": ["Person", "Named"],
+      "statics": { "species": "\"human\"" }
+    }
+  ]
+}
+"#;
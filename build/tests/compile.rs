@@ -0,0 +1,27 @@
+mod common;
+
+use std::path::Path;
+
+use typechain_build::compile_to;
+
+use common::SCHEMA;
+
+
+/// Compile the generated source with the real macros.
+///
+/// This is the only test in its binary, since the
+/// path to the source is passed to the pass case
+/// through the environment.
+#[test]
+fn test_generated_source_compiles() {
+    let dir = Path::new(env!("CARGO_TARGET_TMPDIR")).join("typechain-build");
+    std::fs::create_dir_all(&dir).unwrap();
+
+    let schema = dir.join("model.json");
+    std::fs::write(&schema, SCHEMA).unwrap();
+
+    let out = compile_to(&schema, &dir).unwrap();
+    std::env::set_var("TYPECHAIN_BUILD_GENERATED", &out);
+
+    trybuild::TestCases::new().pass("tests/pass/generated.rs");
+}
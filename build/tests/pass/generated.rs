@@ -0,0 +1,20 @@
+include!(env!("TYPECHAIN_BUILD_GENERATED"));
+
+fn main() {
+    let mut employee = Employee {
+        name: "Ada".to_string(),
+        age: 36
+    };
+
+    *employee.age() += 1;
+
+    let person: &mut Person = &mut employee;
+
+    assert_eq!(person.name(), "Ada");
+    assert_eq!(*person.age(), 37);
+    assert_eq!(person.species(), "human");
+
+    let named: &Named = &employee;
+
+    assert_eq!(named.name(), "Ada");
+}
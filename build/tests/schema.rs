@@ -0,0 +1,93 @@
+mod common;
+
+use typechain_build::{Schema, Error, compile_to};
+
+use common::SCHEMA;
+
+
+const EXPECTED: &str = r#"::typechain::chainlink!(Person => {
+    const name: String;
+    mut age: u8;
+    static species: &'static str;
+});
+
+::typechain::chainlink!(Named => {
+    const name: String;
+});
+
+::typechain::chain!(Employee => {
+    @Person @Named
+    const name: String;
+
+    @Person
+    mut age: u8;
+
+    @Person
+    static species: &'static str = "human";
+});
+
+"#;
+
+/// Get the message of a schema error.
+fn schema_error(source: &str) -> String {
+    match Schema::parse(source).unwrap().to_rust() {
+        Err(Error::Schema(message)) => message,
+        other => panic!("expected a schema error, got {:?}", other)
+    }
+}
+
+#[test]
+fn test_to_rust() {
+    assert_eq!(Schema::parse(SCHEMA).unwrap().to_rust().unwrap(), EXPECTED);
+}
+
+#[test]
+fn test_compile_to() {
+    let dir = std::env::temp_dir().join(format!("typechain-build-{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+
+    let schema = dir.join("model.json");
+    std::fs::write(&schema, SCHEMA).unwrap();
+
+    let out = compile_to(&schema, &dir).unwrap();
+    let source = std::fs::read_to_string(&out).unwrap();
+
+    assert_eq!(out, dir.join("model.rs"));
+    assert!(source.ends_with(EXPECTED));
+
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn test_errors() {
+    assert!(matches!(Schema::parse(r#"{ "chains": [{ "name": "A", "parent": [] }] }"#), Err(Error::Json(_))));
+
+    assert_eq!(
+        schema_error(r#"{ "chains": [{ "name": "A", "parents": ["Missing"] }] }"#),
+        "chain `A` implements `Missing`, which is not a chainlink in the schema"
+    );
+
+    assert_eq!(
+        schema_error(r#"{
+            "chainlinks": [
+                { "name": "X", "fields": [{ "name": "id", "kind": "const", "type": "u32" }] },
+                { "name": "Y", "fields": [{ "name": "id", "kind": "mut", "type": "u32" }] }
+            ],
+            "chains": [{ "name": "A", "parents": ["X", "Y"] }]
+        }"#),
+        "chain `A` cannot implement field `id` of both `X` and `Y`, because it is `const u32` in one and `mut u32` in the other"
+    );
+
+    assert_eq!(
+        schema_error(r#"{
+            "chainlinks": [{ "name": "X", "fields": [{ "name": "tag", "kind": "static", "type": "u8" }] }],
+            "chains": [{ "name": "A", "parents": ["X"] }]
+        }"#),
+        "chain `A` does not set static item `tag`"
+    );
+
+    assert_eq!(
+        schema_error(r#"{ "chainlinks": [{ "name": "X", "fields": [{ "name": "id", "kind": "const", "type": "Vec<" }] }] }"#),
+        "type `Vec<` of field `id` is not a valid Rust type"
+    );
+}
@@ -0,0 +1,59 @@
+//! Print the graph of chainlinks and chains in a crate.
+//!
+//! ```text
+//! typechain-graph [--dot | --mermaid] [PATH]...
+//! ```
+//!
+//! Every path is a file or a directory of `.rs` files,
+//! and defaults to `src`. The graph is printed in the
+//! Graphviz DOT language unless `--mermaid` is given.
+
+use std::path::PathBuf;
+use std::process::ExitCode;
+
+use typechain_codegen::graph::Graph;
+
+
+const USAGE: &str = "usage: typechain-graph [--dot | --mermaid] [PATH]...";
+
+fn main() -> ExitCode {
+    let mut mermaid = false;
+    let mut paths = Vec::new();
+
+    for arg in std::env::args().skip(1) {
+        match arg.as_str() {
+            "--dot" => mermaid = false,
+            "--mermaid" => mermaid = true,
+            "-h" | "--help" => {
+                println!("{}", USAGE);
+                return ExitCode::SUCCESS;
+            },
+            flag if flag.starts_with('-') => {
+                eprintln!("unknown option `{}`\n{}", flag, USAGE);
+                return ExitCode::FAILURE;
+            },
+            path => paths.push(PathBuf::from(path))
+        }
+    }
+
+    if paths.is_empty() {
+        paths.push(PathBuf::from("src"));
+    }
+
+    let mut graph = Graph::new();
+
+    for path in paths {
+        if let Err(err) = graph.add_path(&path) {
+            eprintln!("error: {}: {}", path.display(), err);
+            return ExitCode::FAILURE;
+        }
+    }
+
+    if mermaid {
+        print!("{}", graph.to_mermaid());
+    } else {
+        print!("{}", graph.to_dot());
+    }
+
+    ExitCode::SUCCESS
+}
@@ -0,0 +1,209 @@
+//! Error reporting for expansions.
+//!
+//! Expansions report as many errors as they can
+//! before giving up, so errors are collected while
+//! expanding and returned together once it ends.
+//! [`emit_error!`] records an error and carries on,
+//! while [`abort!`] and [`abort_if_dirty`] stop the
+//! expansion.
+
+use std::cell::RefCell;
+use std::panic::{self, AssertUnwindSafe};
+
+use proc_macro2::Span;
+use quote::ToTokens;
+
+thread_local! {
+    static ERRORS: RefCell<Vec<syn::Error>> = const { RefCell::new(Vec::new()) };
+}
+
+/// The payload used to unwind out of an aborted
+/// expansion.
+struct Abort;
+
+/// The first and last spans of the tokens an error
+/// points at.
+#[derive(Clone, Copy)]
+pub struct SpanRange {
+    first: Span,
+    last: Span
+}
+
+/// Get the spans an error points at, from either a
+/// [`Span`] or anything that can be turned into tokens.
+///
+/// This is called on a reference, so method resolution
+/// picks the [`Span`] impl before the generic one.
+pub trait ToSpanRange {
+    /// Get the span range.
+    fn span_range(self) -> SpanRange;
+}
+
+impl ToSpanRange for Span {
+    fn span_range(self) -> SpanRange {
+        SpanRange { first: self, last: self }
+    }
+}
+
+impl<T: ToTokens> ToSpanRange for &T {
+    fn span_range(self) -> SpanRange {
+        let mut tokens = self.to_token_stream().into_iter();
+        let first = tokens.next().map_or_else(Span::call_site, |t| t.span());
+        let last = tokens.last().map_or(first, |t| t.span());
+
+        SpanRange { first, last }
+    }
+}
+
+/// An error with notes and help messages.
+pub struct Diagnostic {
+    span: SpanRange,
+    message: String,
+    children: Vec<(&'static str, Option<SpanRange>, String)>
+}
+
+impl Diagnostic {
+    /// Create an error pointing at `span`.
+    pub fn spanned(span: SpanRange, message: impl Into<String>) -> Self {
+        Self {
+            span,
+            message: message.into(),
+            children: Vec::new()
+        }
+    }
+
+    /// Add a note to the error.
+    pub fn note(self, message: impl Into<String>) -> Self {
+        self.child("note", None, message)
+    }
+
+    /// Add a note or help message, pointing at `span`
+    /// if it is given and at the error otherwise.
+    pub fn child(mut self, kind: &'static str, span: Option<SpanRange>, message: impl Into<String>) -> Self {
+        self.children.push((kind, span, message.into()));
+        self
+    }
+
+    /// Record the error, continuing the expansion.
+    pub fn emit(self) {
+        let error = self.into_error();
+
+        ERRORS.with(|errors| errors.borrow_mut().push(error));
+    }
+
+    /// Record the error and stop the expansion.
+    pub fn abort(self) -> ! {
+        self.emit();
+        panic::resume_unwind(Box::new(Abort))
+    }
+
+    /// Convert the error to a [`syn::Error`].
+    ///
+    /// Stable compilers cannot attach notes to an error,
+    /// so each one is reported as its own message right
+    /// after it, where it can point somewhere else.
+    fn into_error(self) -> syn::Error {
+        let mut error = spanned_error(self.span, self.message);
+
+        for (kind, span, message) in self.children {
+            error.combine(spanned_error(span.unwrap_or(self.span), format!("{}: {}", kind, message)));
+        }
+
+        error
+    }
+}
+
+fn spanned_error(span: SpanRange, message: String) -> syn::Error {
+    let first = syn::Ident::new("first", span.first);
+    let last = syn::Ident::new("last", span.last);
+
+    syn::Error::new_spanned(quote::quote! { #first #last }, message)
+}
+
+/// Record an error and continue the expansion.
+///
+/// This takes the span, a format string with its
+/// arguments, and then any `note = ...` or
+/// `help = ...` messages, separated by `;`. Notes
+/// can point somewhere else than the error, as in
+/// `note = span => "first given here"`.
+macro_rules! emit_error {
+    ($($args:tt)*) => {
+        $crate::diagnostic::diagnostic!($($args)*).emit()
+    };
+}
+
+/// Record an error and stop the expansion.
+///
+/// This takes the same arguments as [`emit_error!`].
+macro_rules! abort {
+    ($($args:tt)*) => {
+        $crate::diagnostic::diagnostic!($($args)*).abort()
+    };
+}
+
+/// Build a [`Diagnostic`] from the arguments of
+/// [`emit_error!`].
+macro_rules! diagnostic {
+    (@children $diagnostic:expr $(;)?) => {
+        $diagnostic
+    };
+    (@children $diagnostic:expr; $kind:ident = $span:expr => $note:literal $(, $note_arg:expr)* $(; $($rest:tt)*)?) => {
+        $crate::diagnostic::diagnostic!(
+            @children $diagnostic.child(stringify!($kind), Some((&$span).span_range()), format!($note $(, $note_arg)*))
+            $(; $($rest)*)?
+        )
+    };
+    (@children $diagnostic:expr; $kind:ident = $note:literal $(, $note_arg:expr)* $(; $($rest:tt)*)?) => {
+        $crate::diagnostic::diagnostic!(
+            @children $diagnostic.child(stringify!($kind), None, format!($note $(, $note_arg)*))
+            $(; $($rest)*)?
+        )
+    };
+    ($span:expr, $fmt:literal $(, $arg:expr)* $(; $($rest:tt)*)?) => {{
+        #[allow(unused_imports)]
+        use $crate::diagnostic::ToSpanRange as _;
+
+        $crate::diagnostic::diagnostic!(
+            @children $crate::diagnostic::Diagnostic::spanned((&$span).span_range(), format!($fmt $(, $arg)*))
+            $(; $($rest)*)?
+        )
+    }};
+}
+
+pub(crate) use {abort, diagnostic, emit_error};
+
+/// Stop the expansion if any errors were recorded.
+pub fn abort_if_dirty() {
+    if ERRORS.with(|errors| !errors.borrow().is_empty()) {
+        panic::resume_unwind(Box::new(Abort));
+    }
+}
+
+/// Run an expansion, returning its output or every
+/// error it recorded.
+pub fn expand<T>(f: impl FnOnce() -> syn::Result<T>) -> syn::Result<T> {
+    ERRORS.with(|errors| errors.borrow_mut().clear());
+
+    let result = match panic::catch_unwind(AssertUnwindSafe(f)) {
+        Ok(result) => Some(result),
+        Err(payload) if payload.is::<Abort>() => None,
+        Err(payload) => panic::resume_unwind(payload)
+    };
+
+    let mut errors = ERRORS.with(|errors| errors.take());
+
+    match result {
+        Some(Ok(value)) if errors.is_empty() => return Ok(value),
+        Some(Err(err)) => errors.push(err),
+        _ => {}
+    }
+
+    // Aborting always records an error first.
+    let mut errors = errors.into_iter();
+    let mut first = errors.next().expect("an aborted expansion should have errors");
+
+    first.extend(errors);
+
+    Err(first)
+}
@@ -0,0 +1,508 @@
+//! Graphs of chainlinks and the chains implementing
+//! them.
+//!
+//! A [`Graph`] is built by scanning Rust sources for
+//! `chainlink!`, `chain!`, `chain_enum!` and
+//! `impl_chains!` invocations, `#[chainlink]` traits,
+//! and `#[chain]` or `#[derive(Chain)]` structs. It
+//! can be rendered as a Graphviz DOT graph with
+//! [`Graph::to_dot`] or as a Mermaid class diagram
+//! with [`Graph::to_mermaid`].
+
+use std::fs;
+use std::io;
+use std::path::Path;
+
+use proc_macro2::TokenStream;
+use syn::punctuated::Punctuated;
+use syn::visit::Visit;
+use syn::Token;
+
+use crate::display;
+use crate::diagnostic;
+use crate::parse::{self, ChainlinkField, ChainFieldData, ChainShape};
+
+
+/// A chainlink and its items.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Chainlink {
+    /// The name of the chainlink.
+    pub name: String,
+    /// The items of the chainlink, as written in
+    /// `chainlink!`.
+    pub items: Vec<String>
+}
+
+/// A chain, or a type implementing chainlinks with
+/// `impl_chains!`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Chain {
+    /// The name of the chain or type.
+    pub name: String,
+    /// The fields of the chain. This is empty for
+    /// types from `impl_chains!`.
+    pub fields: Vec<String>
+}
+
+/// How a chain implements a chainlink.
+#[derive(Debug, Clone, PartialEq)]
+pub enum EdgeKind {
+    /// The chain implements the chainlink with its
+    /// own items.
+    Implements,
+    /// The chain forwards the chainlink to a field.
+    Delegates(String),
+    /// The chain forwards the chainlink through
+    /// `Deref`.
+    Derefs,
+    /// The chain flattens another chain in a field,
+    /// implementing all of its chainlinks.
+    Flattens(String)
+}
+
+/// An edge from a chain to a chainlink it implements,
+/// or to a chain it flattens.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Edge {
+    /// The name of the chain.
+    pub from: String,
+    /// The name of the chainlink or flattened chain.
+    pub to: String,
+    /// How the chain implements it.
+    pub kind: EdgeKind
+}
+
+/// A graph of chainlinks, chains and the edges
+/// between them.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Graph {
+    /// The chainlinks in the graph.
+    pub chainlinks: Vec<Chainlink>,
+    /// The chains in the graph.
+    pub chains: Vec<Chain>,
+    /// The edges from chains to chainlinks and
+    /// flattened chains.
+    pub edges: Vec<Edge>
+}
+
+/// Find the invocations of typechain macros in a file.
+struct FindMacros<'a>(&'a mut Graph);
+
+impl<'ast> Visit<'ast> for FindMacros<'_> {
+    fn visit_macro(&mut self, mac: &'ast syn::Macro) {
+        if let Some(name) = mac.path.segments.last() {
+            self.0.add_macro(&name.ident.to_string(), mac.tokens.clone());
+        }
+    }
+
+    fn visit_item_struct(&mut self, item: &'ast syn::ItemStruct) {
+        self.0.add_struct(item);
+
+        syn::visit::visit_item_struct(self, item);
+    }
+
+    fn visit_item_trait(&mut self, item: &'ast syn::ItemTrait) {
+        self.0.add_trait(item);
+
+        syn::visit::visit_item_trait(self, item);
+    }
+}
+
+/// Check whether an attribute is `#[name]`, under
+/// any path, as in `#[typechain::name]`.
+fn is_attribute(attr: &syn::Attribute, name: &str) -> bool {
+    attr.path().segments.last().is_some_and(|s| s.ident == name)
+}
+
+/// Check whether an attribute derives `Chain`.
+fn is_chain_derive(attr: &syn::Attribute) -> bool {
+    if !attr.path().is_ident("derive") {
+        return false;
+    }
+
+    attr.parse_args_with(Punctuated::<syn::Path, Token![,]>::parse_terminated)
+        .is_ok_and(|paths| paths.iter().any(|path| path.segments.last().is_some_and(|s| s.ident == "Chain")))
+}
+
+impl Graph {
+    /// Create an empty graph.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Scan a file, or every `.rs` file in a directory,
+    /// for typechain macros.
+    pub fn scan(path: impl AsRef<Path>) -> io::Result<Self> {
+        let mut graph = Self::new();
+
+        graph.add_path(path.as_ref())?;
+
+        Ok(graph)
+    }
+
+    /// Add the typechain macros in a file, or in every
+    /// `.rs` file in a directory, to the graph.
+    ///
+    /// Directories are read in order of file name, so
+    /// the graph is the same on every platform.
+    pub fn add_path(&mut self, path: &Path) -> io::Result<()> {
+        if path.is_dir() {
+            let mut entries = fs::read_dir(path)?.map(|e| e.map(|e| e.path())).collect::<io::Result<Vec<_>>>()?;
+            entries.sort();
+
+            for entry in entries {
+                if entry.is_dir() || entry.extension().is_some_and(|e| e == "rs") {
+                    self.add_path(&entry)?;
+                }
+            }
+
+            return Ok(());
+        }
+
+        let source = fs::read_to_string(path)?;
+
+        self.add_source(&source).map_err(|err| {
+            io::Error::new(io::ErrorKind::InvalidData, format!("{}: {}", path.display(), err))
+        })
+    }
+
+    /// Add the typechain macros in the source of a
+    /// file to the graph.
+    ///
+    /// Invocations that cannot be parsed are skipped,
+    /// since they may be other macros of the same name.
+    pub fn add_source(&mut self, source: &str) -> syn::Result<()> {
+        let file = syn::parse_file(source)?;
+
+        FindMacros(self).visit_file(&file);
+
+        Ok(())
+    }
+
+    /// Add a macro invocation to the graph, if it is
+    /// one of the typechain macros.
+    fn add_macro(&mut self, name: &str, tokens: TokenStream) {
+        match name {
+            "chainlink" => {
+                if let Ok(ast) = diagnostic::expand(|| syn::parse2::<parse::Chainlink>(tokens)) {
+                    self.add_chainlink(&ast);
+                }
+            },
+            "chain" => {
+                if let Ok(ast) = diagnostic::expand(|| syn::parse2::<parse::Chain>(tokens)) {
+                    self.add_chain(&ast);
+                }
+            },
+            "chain_enum" => {
+                if let Ok(ast) = diagnostic::expand(|| syn::parse2::<parse::ChainEnum>(tokens)) {
+                    self.add_chain_enum(&ast);
+                }
+            },
+            "impl_chains" => {
+                if let Ok(ast) = diagnostic::expand(|| syn::parse2::<parse::ImplChains>(tokens)) {
+                    self.add_impl_chains(&ast);
+                }
+            },
+            _ => {}
+        }
+    }
+
+    /// Add an edge, unless the graph already has it.
+    /// 
+    /// Edges point at the name of a chainlink or chain,
+    /// without its module or generics, so they meet
+    /// the node it was defined as.
+    fn add_edge(&mut self, from: &str, to: &syn::Path, kind: EdgeKind) {
+        let edge = Edge {
+            from: from.to_string(),
+            to: to.segments.last().map(|s| s.ident.to_string()).unwrap_or_default(),
+            kind
+        };
+
+        if !self.edges.contains(&edge) {
+            self.edges.push(edge);
+        }
+    }
+
+    /// Add a `chainlink!` invocation to the graph.
+    fn add_chainlink(&mut self, ast: &parse::Chainlink) {
+        let items = ast.fields.iter().map(|field| match field {
+            ChainlinkField::Const(name, ty) => format!("const {}: {}", name, display(ty)),
+            ChainlinkField::Mut(name, ty) => format!("mut {}: {}", name, display(ty)),
+            ChainlinkField::Static(name, ty) => format!("static {}: {}", name, display(ty)),
+            ChainlinkField::Fn(func) => display(&func.sig).replace(" : ", ": "),
+            ChainlinkField::Type(ty) => format!("type {}", ty.ident)
+        }).collect();
+
+        self.chainlinks.push(Chainlink {
+            name: ast.name.to_string(),
+            items
+        });
+    }
+
+    /// Add a `chain!` invocation to the graph.
+    fn add_chain(&mut self, ast: &parse::Chain) {
+        let name = ast.name.to_string();
+        let mut fields = Vec::new();
+
+        if let ChainShape::Tuple(tuple) = &ast.shape {
+            for (i, field) in tuple.iter().enumerate() {
+                fields.push(format!("{}: {}", i, display(&field.ty)));
+
+                for parent in field.parents.iter() {
+                    self.add_edge(&name, parent, EdgeKind::Implements);
+                }
+            }
+        }
+
+        // Enums list their variants instead of fields.
+        for variant in ast.variants.iter().flatten() {
+            fields.push(variant.name.to_string());
+        }
+
+        let variant_fields = ast.variants.iter().flatten().flat_map(|v| v.fields.iter());
+
+        for field in ast.fields.iter().chain(variant_fields) {
+            match &field.field {
+                ChainFieldData::Const(_, field, ty) | ChainFieldData::Mut(field, ty) if ast.variants.is_none() => {
+                    fields.push(format!("{}: {}", field, display(ty)));
+                },
+                ChainFieldData::Delegate(_, field, ty) | ChainFieldData::Flatten(_, field, ty) => {
+                    fields.push(format!("{}: {}", field, display(ty)));
+                },
+                _ => {}
+            }
+
+            self.add_field_edges(&name, field);
+        }
+
+        self.chains.push(Chain { name, fields });
+    }
+
+    /// Add the edges from a chain to the chainlinks
+    /// one of its fields implements, or to the chain
+    /// it flattens.
+    fn add_field_edges(&mut self, name: &str, field: &parse::ChainField) {
+        let kind = match &field.field {
+            ChainFieldData::Const(..) | ChainFieldData::Mut(..) | ChainFieldData::Static(..) => EdgeKind::Implements,
+            ChainFieldData::Delegate(_, field, _) => EdgeKind::Delegates(field.to_string()),
+            ChainFieldData::Flatten(_, field, ty) => {
+                if let syn::Type::Path(ty) = ty {
+                    self.add_edge(name, &ty.path, EdgeKind::Flattens(field.to_string()));
+                }
+
+                return;
+            }
+        };
+
+        for parent in field.parents.iter() {
+            self.add_edge(name, parent, kind.clone());
+        }
+    }
+
+    /// Add a struct to the graph, if it is a chain from
+    /// `#[derive(Chain)]` or the `#[chain]` attribute.
+    ///
+    /// Its fields are listed whether or not they are
+    /// linked, as they are all fields of the chain.
+    fn add_struct(&mut self, item: &syn::ItemStruct) {
+        let attr_name = if item.attrs.iter().any(is_chain_derive) {
+            "chain"
+        } else if item.attrs.iter().any(|attr| is_attribute(attr, "chain") && matches!(attr.meta, syn::Meta::Path(_))) {
+            "link"
+        } else {
+            return;
+        };
+
+        let Ok(chain_fields) = diagnostic::expand(|| Ok(crate::attribute_fields(attr_name, &item.ident, &item.attrs, &item.fields))) else {
+            return;
+        };
+
+        let name = item.ident.to_string();
+        let fields = item.fields.iter().filter_map(|field| {
+            field.ident.as_ref().map(|ident| format!("{}: {}", ident, display(&field.ty)))
+        }).collect();
+
+        for field in chain_fields.iter() {
+            self.add_field_edges(&name, field);
+        }
+
+        self.chains.push(Chain { name, fields });
+    }
+
+    /// Add a trait to the graph, if it is a chainlink
+    /// from the `#[chainlink]` attribute.
+    fn add_trait(&mut self, item: &syn::ItemTrait) {
+        if !item.attrs.iter().any(|attr| is_attribute(attr, "chainlink")) {
+            return;
+        }
+
+        let mut items = item.items.clone();
+
+        let Ok(fields) = diagnostic::expand(|| Ok(crate::trait_fields(&mut items))) else {
+            return;
+        };
+
+        self.add_chainlink(&parse::Chainlink {
+            pointers: false,
+            sealed: None,
+            name: item.ident.clone(),
+            generics: vec![],
+            supertraits: vec![],
+            fields
+        });
+    }
+
+    /// Add a `chain_enum!` invocation to the graph.
+    /// The enum lists its variants as fields, named
+    /// after the chains they hold.
+    fn add_chain_enum(&mut self, ast: &parse::ChainEnum) {
+        let name = ast.name.to_string();
+        let fields = ast.variants.iter().filter_map(|v| v.segments.last().map(|s| s.ident.to_string())).collect();
+
+        self.add_edge(&name, &ast.chain, EdgeKind::Implements);
+        self.chains.push(Chain { name, fields });
+    }
+
+    /// Add an `impl_chains!` invocation to the graph.
+    fn add_impl_chains(&mut self, ast: &parse::ImplChains) {
+        let name = display(&ast.ty);
+
+        for chain in ast.impls.iter().map(|i| &i.chain).chain(ast.fields.iter().filter_map(|f| f.chain.as_ref())) {
+            self.add_edge(&name, chain, EdgeKind::Implements);
+        }
+
+        for delegate in ast.delegates.iter() {
+            self.add_edge(&name, &delegate.chain, EdgeKind::Delegates(display(&delegate.member)));
+        }
+
+        for deref in ast.derefs.iter() {
+            for chain in deref.chains.iter() {
+                self.add_edge(&name, chain, EdgeKind::Derefs);
+            }
+        }
+
+        if !self.chains.iter().any(|c| c.name == name) {
+            self.chains.push(Chain { name, fields: vec![] });
+        }
+    }
+
+    /// Render the graph in the Graphviz DOT language.
+    ///
+    /// Chainlinks and chains are record nodes listing
+    /// their items and fields. Implemented chainlinks
+    /// are solid edges, delegated and dereferenced
+    /// chainlinks are dashed, and flattened chains have
+    /// a diamond head.
+    pub fn to_dot(&self) -> String {
+        /// Escape text for a record label.
+        fn escape(text: &str) -> String {
+            text.chars().fold(String::new(), |mut out, c| {
+                if "{}|<>\"\\".contains(c) {
+                    out.push('\\');
+                }
+
+                out.push(c);
+                out
+            })
+        }
+
+        /// Render a record label with a title and lines.
+        fn record(title: &str, lines: &[String]) -> String {
+            let lines = lines.iter().map(|l| format!("{}\\l", escape(l))).collect::<String>();
+
+            format!("{{{}|{}}}", title, lines)
+        }
+
+        let mut dot = String::from("digraph typechain {\n    node [shape=record];\n");
+
+        for chainlink in self.chainlinks.iter() {
+            let title = format!("\\<\\<chainlink\\>\\>\\n{}", escape(&chainlink.name));
+
+            dot.push_str(&format!("    \"{}\" [label=\"{}\"];\n", chainlink.name.replace('"', "\\\""), record(&title, &chainlink.items)));
+        }
+
+        for chain in self.chains.iter() {
+            dot.push_str(&format!("    \"{}\" [label=\"{}\"];\n", chain.name.replace('"', "\\\""), record(&escape(&chain.name), &chain.fields)));
+        }
+
+        for edge in self.edges.iter() {
+            let attrs = match &edge.kind {
+                EdgeKind::Implements => String::from("arrowhead=empty"),
+                EdgeKind::Delegates(field) => format!("arrowhead=empty, style=dashed, label=\"delegate {}\"", field),
+                EdgeKind::Derefs => String::from("arrowhead=empty, style=dashed, label=\"deref\""),
+                EdgeKind::Flattens(field) => format!("arrowhead=diamond, label=\"flatten {}\"", field)
+            };
+
+            dot.push_str(&format!("    \"{}\" -> \"{}\" [{}];\n", edge.from.replace('"', "\\\""), edge.to.replace('"', "\\\""), attrs));
+        }
+
+        dot.push_str("}\n");
+
+        dot
+    }
+
+    /// Render the graph as a Mermaid class diagram.
+    ///
+    /// Chainlinks are classes with a `<<chainlink>>`
+    /// annotation. Implemented chainlinks are
+    /// realization edges, delegated and dereferenced
+    /// chainlinks are labelled dependencies, and
+    /// flattened chains are compositions.
+    pub fn to_mermaid(&self) -> String {
+        /// Mermaid writes generics between `~`.
+        fn generics(text: &str) -> String {
+            text.replace("->", "\0").replace(['<', '>'], "~").replace('\0', "->")
+        }
+
+        /// Names with anything but word characters are
+        /// quoted with backticks.
+        fn class(name: &str) -> String {
+            if name.chars().all(|c| c.is_alphanumeric() || c == '_') {
+                name.to_string()
+            } else {
+                format!("`{}`", name)
+            }
+        }
+
+        let mut mermaid = String::from("classDiagram\n");
+
+        for chainlink in self.chainlinks.iter() {
+            mermaid.push_str(&format!("    class {} {{\n        <<chainlink>>\n", class(&chainlink.name)));
+
+            for item in chainlink.items.iter() {
+                mermaid.push_str(&format!("        {}\n", generics(item)));
+            }
+
+            mermaid.push_str("    }\n");
+        }
+
+        for chain in self.chains.iter() {
+            if chain.fields.is_empty() {
+                mermaid.push_str(&format!("    class {}\n", class(&chain.name)));
+                continue;
+            }
+
+            mermaid.push_str(&format!("    class {} {{\n", class(&chain.name)));
+
+            for field in chain.fields.iter() {
+                mermaid.push_str(&format!("        {}\n", generics(field)));
+            }
+
+            mermaid.push_str("    }\n");
+        }
+
+        for edge in self.edges.iter() {
+            let (from, to) = (class(&edge.from), class(&edge.to));
+
+            mermaid.push_str(&match &edge.kind {
+                EdgeKind::Implements => format!("    {} ..|> {}\n", from, to),
+                EdgeKind::Delegates(field) => format!("    {} ..> {} : delegate {}\n", from, to, field),
+                EdgeKind::Derefs => format!("    {} ..> {} : deref\n", from, to),
+                EdgeKind::Flattens(field) => format!("    {} *-- {} : flatten {}\n", from, to, field)
+            });
+        }
+
+        mermaid
+    }
+}
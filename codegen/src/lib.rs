@@ -0,0 +1,2267 @@
+#![deny(missing_docs)]
+
+//! # `typechain-codegen`
+//! 
+//! This crate contains the code generation behind
+//! the macros in
+//! [`typechain-macros`](https://crates.io/crates/typechain-macros).
+//! Each macro has an `expand_*` function here that
+//! takes the tokens of an invocation and returns its
+//! expansion, so the same code can be used from build
+//! scripts and tests, outside of a procedural macro.
+//! 
+//! Errors are returned as a [`syn::Error`], which
+//! holds every error found in the invocation and can
+//! be turned into `compile_error!` invocations with
+//! [`syn::Error::to_compile_error`].
+//! 
+//! The [`graph`] module uses the same parsers to find
+//! the chainlinks and chains in a crate and render
+//! them as a diagram. See the
+//! [`typechain`](https://crates.io/crates/typechain)
+//! crate for more information.
+
+use std::collections::HashMap;
+
+use diagnostic::{emit_error, abort, abort_if_dirty, Diagnostic, ToSpanRange};
+use parse::{ChainlinkField, ChainFieldData, ChainShape, ImplStrategy, ImplFieldKind, ImplSource};
+use proc_macro2::{Span, TokenStream};
+use quote::{quote, ToTokens, quote_spanned, format_ident};
+use syn::{Path, spanned::Spanned, Visibility, Type, Member, TypeParamBound, GenericArgument, PathArguments, visit::Visit, visit_mut::VisitMut};
+
+mod diagnostic;
+pub mod graph;
+mod parse;
+
+
+/// Get the path of the trait generated for a chainlink.
+fn chainlink_trait(path: &Path) -> Path {
+    let mut path = path.clone();
+    path.segments.last_mut().unwrap().ident = syn::Ident::new(&format!("{}Chainlink", path.segments.last().unwrap().ident), path.span());
+
+    path
+}
+
+/// Get the path of the metadata macro generated
+/// for a chainlink or chain.
+fn meta_path(path: &Path) -> Path {
+    let mut path = path.clone();
+    path.segments.last_mut().unwrap().arguments = PathArguments::None;
+
+    path
+}
+
+/// Get a crate-unique name for the metadata macro
+/// generated for a chainlink or chain.
+/// 
+/// The metadata macro is exported from the crate root,
+/// so its name cannot depend on the module it is in.
+fn meta_ident(kind: &str, name: &syn::Ident, raw: &TokenStream) -> syn::Ident {
+    let hash = fnv1a(&format!("{}\0{:?}", raw, Span::call_site()));
+
+    format_ident!("__typechain_{}_{}_{:x}", kind, name, hash)
+}
+
+/// Hash text with 64-bit FNV-1a.
+/// 
+/// Unlike the hashers in `std`, this gives the same
+/// value on every toolchain, so expansions can be
+/// reproduced.
+fn fnv1a(text: &str) -> u64 {
+    text.bytes().fold(0xcbf2_9ce4_8422_2325, |hash, byte| {
+        (hash ^ u64::from(byte)).wrapping_mul(0x0100_0000_01b3)
+    })
+}
+
+/// Generate the metadata macro for a chain, which
+/// records the chainlinks it implements and the
+/// chains flattened into it.
+fn chain_meta_macro(name: &syn::Ident, generics: &TokenStream, raw: &TokenStream, parents: &[Path], flattened: &[Type]) -> TokenStream {
+    let meta_name = meta_ident("chain", name, raw);
+
+    quote! {
+        #[doc(hidden)]
+        #[macro_export]
+        macro_rules! #meta_name {
+            ($callback:path { $($args:tt)* }) => {
+                $callback! { { $($args)* } #name #generics => [#(#parents),*] [#(#flattened),*] }
+            };
+        }
+
+        // Chains in private modules may never be
+        // flattened.
+        #[doc(hidden)]
+        #[allow(unused_imports)]
+        pub use #meta_name as #name;
+    }
+}
+
+/// Describe a field in a `typechain::Schema`.
+fn schema_field(name: impl std::fmt::Display, kind: &str, ty: &Type) -> TokenStream {
+    let name = name.to_string();
+    let kind = format_ident!("{}", kind);
+    let ty = display(ty);
+
+    quote! {
+        ::typechain::SchemaField::__new(#name, ::typechain::FieldKind::#kind, #ty)
+    }
+}
+
+/// Describe the fields of a chain in a `typechain::Schema`.
+/// 
+/// Statics given by their chainlink alone are left
+/// out, since only the chainlink knows their names
+/// and types.
+fn schema_chain_fields(fields: &[parse::ChainField]) -> Vec<TokenStream> {
+    fields.iter().filter_map(|f| match &f.field {
+        ChainFieldData::Const(_, name, ty) => Some(schema_field(name, "Const", ty)),
+        ChainFieldData::Mut(name, ty) => Some(schema_field(name, "Mut", ty)),
+        ChainFieldData::Static(_, Type::Infer(_), _) => None,
+        ChainFieldData::Static(name, ty, _) => Some(schema_field(name, "Static", ty)),
+        ChainFieldData::Delegate(_, name, ty) => Some(schema_field(name, "Delegate", ty)),
+        ChainFieldData::Flatten(_, name, ty) => Some(schema_field(name, "Flatten", ty))
+    }).collect()
+}
+
+/// Generate the `SCHEMA` constant describing a
+/// chainlink or chain, for its `Described` impl.
+/// 
+/// Only type parameters are listed from `generics`,
+/// since lifetimes and constants do not describe data.
+fn schema_const(name: &syn::Ident, kind: &str, generics: &TokenStream, fields: &[TokenStream], variants: &[TokenStream], chainlinks: &[Path]) -> TokenStream {
+    let name = name.to_string();
+    let kind = format_ident!("{}", kind);
+    let chainlinks = chainlinks.iter().map(display);
+
+    let generics = syn::parse2::<syn::Generics>(generics.clone()).map(|generics| {
+        generics.type_params().map(|t| t.ident.to_string()).collect::<Vec<_>>()
+    }).unwrap_or_default();
+
+    quote! {
+        const SCHEMA: ::typechain::Schema = ::typechain::Schema::__new(
+            #name,
+            ::typechain::SchemaKind::#kind,
+            &[#(#generics),*],
+            &[#(#fields),*],
+            &[#(#variants),*],
+            &[#(#chainlinks),*]
+        );
+    }
+}
+
+/// Generate a shorthand item for the `Items` strategy
+/// of [`__impl_chainlink`].
+fn shorthand(field: &parse::ImplField) -> TokenStream {
+    let kind = match field.kind {
+        ImplFieldKind::Const => quote! { const },
+        ImplFieldKind::Mut => quote! { mut },
+        ImplFieldKind::Static => quote! { static }
+    };
+
+    let name = &field.name;
+    let ty = field.ty.iter();
+    let expr = &field.expr;
+
+    quote! {
+        #kind #name #(: #ty)* = #expr;
+    }
+}
+
+/// Group items by the chainlink they belong to.
+/// 
+/// Groups keep the order their chainlink first
+/// appears in, so every build expands the same way.
+fn group_by_chain<T>(items: impl IntoIterator<Item = (Path, T)>) -> Vec<(Path, Vec<T>)> {
+    let mut groups: Vec<(Path, Vec<T>)> = Vec::new();
+
+    for (chain, item) in items {
+        match groups.iter_mut().find(|(c, _)| *c == chain) {
+            Some((_, group)) => group.push(item),
+            None => groups.push((chain, vec![item]))
+        }
+    }
+
+    groups
+}
+
+/// Replace a chainlink's generic parameters with
+/// the arguments it was referenced with.
+struct Substitute {
+    types: HashMap<syn::Ident, Type>,
+    lifetimes: HashMap<syn::Ident, syn::Lifetime>
+}
+
+impl Substitute {
+    fn new(params: &[TypeParamBound], path: &Path) -> Self {
+        let mut types = HashMap::new();
+        let mut lifetimes = HashMap::new();
+
+        let args = match &path.segments.last().unwrap().arguments {
+            PathArguments::AngleBracketed(args) => args.args.iter().cloned().collect(),
+            _ => vec![]
+        };
+
+        let mut type_args = args.iter().filter_map(|a| match a {
+            GenericArgument::Type(ty) => Some(ty.clone()),
+            _ => None
+        });
+        let mut lifetime_args = args.iter().filter_map(|a| match a {
+            GenericArgument::Lifetime(l) => Some(l.clone()),
+            _ => None
+        });
+
+        for param in params {
+            match param {
+                TypeParamBound::Lifetime(l) => {
+                    if let Some(arg) = lifetime_args.next() {
+                        lifetimes.insert(l.ident.clone(), arg);
+                    }
+                },
+                TypeParamBound::Trait(t) => {
+                    if let (Some(ident), Some(arg)) = (t.path.get_ident(), type_args.next()) {
+                        types.insert(ident.clone(), arg);
+                    }
+                },
+                _ => {}
+            }
+        }
+
+        Substitute {
+            types,
+            lifetimes
+        }
+    }
+
+    fn field(&mut self, field: &mut ChainlinkField) {
+        match field {
+            ChainlinkField::Const(_, ty) | ChainlinkField::Mut(_, ty) | ChainlinkField::Static(_, ty) => {
+                self.visit_type_mut(ty);
+            },
+            ChainlinkField::Fn(func) => {
+                self.visit_signature_mut(&mut func.sig);
+            },
+            ChainlinkField::Type(ty) => {
+                for bound in ty.bounds.iter_mut() {
+                    self.visit_type_param_bound_mut(bound);
+                }
+            }
+        }
+    }
+}
+
+impl VisitMut for Substitute {
+    fn visit_type_mut(&mut self, ty: &mut Type) {
+        if let Type::Path(path) = ty {
+            if let Some(replacement) = path.qself.is_none().then(|| path.path.get_ident()).flatten().and_then(|i| self.types.get(i)) {
+                *ty = replacement.clone();
+                return;
+            }
+        }
+
+        syn::visit_mut::visit_type_mut(self, ty);
+    }
+
+    fn visit_lifetime_mut(&mut self, lifetime: &mut syn::Lifetime) {
+        if let Some(replacement) = self.lifetimes.get(&lifetime.ident) {
+            *lifetime = replacement.clone();
+        }
+    }
+}
+
+/// Give every `self` in `tokens` the call site span.
+/// 
+/// User expressions reach callbacks through the
+/// metadata macro, so their `self` would otherwise
+/// not resolve to the receiver of the generated
+/// function.
+fn respan_self(tokens: TokenStream) -> TokenStream {
+    tokens.into_iter().map(|tt| match tt {
+        proc_macro2::TokenTree::Ident(ident) if ident == "self" => {
+            proc_macro2::TokenTree::Ident(syn::Ident::new("self", Span::call_site()))
+        },
+        proc_macro2::TokenTree::Group(group) => {
+            let mut new = proc_macro2::Group::new(group.delimiter(), respan_self(group.stream()));
+            new.set_span(group.span());
+
+            proc_macro2::TokenTree::Group(new)
+        },
+        tt => tt
+    }).collect()
+}
+
+/// How a chainlink item takes `self`.
+#[derive(Clone, Copy)]
+enum Receiver {
+    Ref,
+    Mut,
+    Value,
+    None
+}
+
+/// Get the signature of a chainlink item, with its
+/// arguments renamed so they can be forwarded.
+fn forward_signature(field: &ChainlinkField) -> (TokenStream, Receiver, Vec<syn::Ident>) {
+    match field {
+        ChainlinkField::Const(name, ty) => {
+            (quote! { fn #name(&self) -> & #ty }, Receiver::Ref, vec![])
+        },
+        ChainlinkField::Mut(name, ty) => {
+            (quote! { fn #name(&mut self) -> &mut #ty }, Receiver::Mut, vec![])
+        },
+        ChainlinkField::Static(name, ty) => {
+            (quote! { fn #name(&self) -> #ty }, Receiver::Ref, vec![])
+        },
+        ChainlinkField::Fn(func) => {
+            let mut sig = func.sig.clone();
+
+            let mut receiver = Receiver::None;
+            let mut args = Vec::new();
+
+            for (i, input) in sig.inputs.iter_mut().enumerate() {
+                match input {
+                    syn::FnArg::Receiver(recv) => {
+                        if recv.colon_token.is_some() {
+                            emit_error!(recv, "Chainlink functions with typed receivers cannot be forwarded");
+                        }
+
+                        receiver = match (&recv.reference, &recv.mutability) {
+                            (Some(_), Some(_)) => Receiver::Mut,
+                            (Some(_), None) => Receiver::Ref,
+                            (None, _) => Receiver::Value
+                        };
+                    },
+                    syn::FnArg::Typed(arg) => {
+                        let ident = format_ident!("__arg{}", i);
+
+                        *arg.pat = syn::parse_quote! { #ident };
+                        args.push(ident);
+                    }
+                }
+            }
+
+            (respan_self(sig.to_token_stream()), receiver, args)
+        },
+        ChainlinkField::Type(_) => unreachable!("associated types are forwarded by `forward_type`")
+    }
+}
+
+/// Generate an associated type that forwards to
+/// the same type on `target_ty`.
+fn forward_type(ty: &syn::TraitItemType, trait_: &Path, target_ty: &Type) -> TokenStream {
+    if !ty.generics.params.is_empty() {
+        emit_error!(ty.generics, "Generic associated types cannot be forwarded");
+    }
+
+    if let Type::Infer(_) = target_ty {
+        emit_error!(ty.ident, "Associated types cannot be forwarded through `deref`");
+    }
+
+    let name = &ty.ident;
+
+    quote! {
+        type #name = <#target_ty as #trait_>::#name;
+    }
+}
+
+/// Generate a call to a chainlink item on `target`,
+/// which is of type `target_ty`.
+fn forward_call(name: &syn::Ident, receiver: Receiver, args: &[syn::Ident], trait_: &Path, target_ty: &Type, target: &TokenStream) -> TokenStream {
+    let receiver = match receiver {
+        Receiver::Ref => Some(quote! { &#target }),
+        Receiver::Mut => Some(quote! { &mut #target }),
+        Receiver::Value => Some(quote! { #target }),
+        Receiver::None => None
+    }.into_iter();
+
+    quote! {
+        <#target_ty as #trait_>::#name(#(#receiver,)* #(#args),*)
+    }
+}
+
+/// Generate a chainlink item that forwards to the
+/// same item on `target`, which is of type `target_ty`.
+fn forward_field(field: &ChainlinkField, trait_: &Path, target_ty: &Type, target: &TokenStream) -> TokenStream {
+    if let ChainlinkField::Type(ty) = field {
+        return forward_type(ty, trait_, target_ty);
+    }
+
+    let (sig, receiver, args) = forward_signature(field);
+    let call = forward_call(field.name(), receiver, &args, trait_, target_ty, target);
+
+    quote! {
+        #sig {
+            #call
+        }
+    }
+}
+
+/// Generate a chainlink item that matches on an
+/// enum, forwarding to the value in each variant.
+/// 
+/// Associated types are taken from the first variant.
+fn forward_match(field: &ChainlinkField, trait_: &Path, variants: &[(syn::Ident, Type)]) -> TokenStream {
+    if let ChainlinkField::Type(ty) = field {
+        return match variants.first() {
+            Some((_, target_ty)) => forward_type(ty, trait_, target_ty),
+            None => quote! {}
+        };
+    }
+
+    let (sig, receiver, args) = forward_signature(field);
+
+    if let Receiver::None = receiver {
+        emit_error!(field.name(), "Chainlink functions without `self` cannot be matched on");
+    }
+
+    let value = format_ident!("__typechain_value");
+    let target = match receiver {
+        Receiver::Value => quote! { #value },
+        _ => quote! { *#value }
+    };
+
+    let arms = variants.iter().map(|(variant, ty)| {
+        let call = forward_call(field.name(), receiver, &args, trait_, ty, &target);
+
+        quote! {
+            Self::#variant(#value) => #call
+        }
+    });
+
+    quote! {
+        #sig {
+            match self {
+                #(#arms),*
+            }
+        }
+    }
+}
+
+/// Generate implementations of a chainlink for
+/// references and smart pointers to implementors.
+/// 
+/// Shared pointers are skipped if any item needs
+/// exclusive access.
+/// 
+/// `bounds` are extra where predicates that the
+/// trait's generic parameters need.
+fn pointer_impls(ast: &parse::Chainlink, trait_name: &syn::Ident, bounds: &[syn::WherePredicate]) -> TokenStream {
+    let generics = ast.generics.clone();
+    let trait_ = quote! { #trait_name < #( #generics ),* > };
+
+    let exclusive = ast.fields.iter().any(|f| match f {
+        ChainlinkField::Mut(..) => true,
+        ChainlinkField::Fn(func) => func.sig.receiver().is_some_and(|r| r.mutability.is_some()),
+        _ => false
+    });
+
+    for field in ast.fields.iter() {
+        if let ChainlinkField::Fn(func) = field {
+            if func.sig.receiver().is_some_and(|r| r.reference.is_none()) {
+                emit_error!(func.sig.ident, "Chainlink functions taking `self` by value cannot be forwarded through pointers");
+            }
+
+            if requires_sized(&func.sig) {
+                emit_error!(func.sig.ident, "Chainlink functions requiring `Self: Sized` cannot be forwarded through pointers");
+            }
+        }
+    }
+
+    let target_ty = syn::parse_quote! { __T };
+    let access = quote! { **self };
+    let trait_path = syn::parse_quote! { #trait_ };
+
+    let funcs = ast.fields.iter().map(|f| forward_field(f, &trait_path, &target_ty, &access)).collect::<Vec<_>>();
+
+    let mut pointers = vec![
+        quote! { &'__typechain mut __T },
+        quote! { ::std::boxed::Box<__T> }
+    ];
+
+    if !exclusive {
+        pointers.extend([
+            quote! { &'__typechain __T },
+            quote! { ::std::rc::Rc<__T> },
+            quote! { ::std::sync::Arc<__T> }
+        ]);
+    }
+
+    let supertraits = &ast.supertraits;
+
+    let impls = pointers.iter().map(|pointer| {
+        let supertraits = if supertraits.is_empty() {
+            None
+        } else {
+            Some(quote! { #pointer: #( #supertraits )+* })
+        }.into_iter();
+
+        let where_clause = if !bounds.is_empty() || !ast.supertraits.is_empty() {
+            quote! { where #( #bounds, )* #( #supertraits )* }
+        } else {
+            quote! {}
+        };
+
+        quote! {
+            impl<'__typechain, #( #generics, )* __T: ?Sized + #trait_> #trait_ for #pointer #where_clause {
+                #(#funcs)*
+            }
+        }
+    });
+
+    quote! {
+        #(#impls)*
+    }
+}
+
+/// Expand a `chainlink!` invocation.
+pub fn expand_chainlink(input: TokenStream) -> syn::Result<TokenStream> {
+    diagnostic::expand(|| chainlink(input))
+}
+
+/// Create a chainlink trait.
+fn chainlink(input: TokenStream) -> syn::Result<TokenStream> {
+    let raw = input.clone();
+    let ast = syn::parse2::<parse::Chainlink>(input)?;
+
+    let name = ast.name.clone();
+    let generics = ast.generics.clone();
+    let generics = quote! {
+        < #( #generics ),* >
+    };
+
+    let fields = ast.fields.iter().map(|f| {
+        match f {
+            ChainlinkField::Const(name, ty) => {
+                quote! {
+                    fn #name(&self) -> & #ty;
+                }
+            },
+            ChainlinkField::Mut(name, ty) => {
+                quote! {
+                    fn #name(&mut self) -> &mut #ty;
+                }
+            },
+            ChainlinkField::Static(name, ty) => {
+                quote! {
+                    fn #name(&self) -> #ty;
+                }
+            },
+            ChainlinkField::Fn(func) => {
+                let sig = &func.sig;
+
+                quote! {
+                    #sig;
+                }
+            },
+            ChainlinkField::Type(ty) => ty.to_token_stream()
+        }
+    });
+
+    let trait_name = syn::Ident::new(&format!("{}Chainlink", name), Span::call_site());
+    let vis = syn::parse_quote! { pub };
+
+    let (sealed, kind_fn, support) = chainlink_support(&ast, &vis, &[], &raw, &raw);
+
+    let supertraits = sealed.into_iter().chain(ast.supertraits.iter().map(|t| t.to_token_stream())).collect::<Vec<_>>();
+
+    let supertraits = if supertraits.is_empty() {
+        quote! {}
+    } else {
+        quote! { : #( #supertraits )+* }
+    };
+
+    let expanded = quote! {
+        #[allow(missing_docs)]
+        pub trait #trait_name #generics #supertraits {
+            #(#fields)*
+
+            #kind_fn
+        }
+
+        #support
+    };
+
+    Ok(expanded)
+}
+
+/// Expand a `#[chainlink]` attribute, given its
+/// arguments and the trait it is on.
+pub fn expand_chainlink_attr(attr: TokenStream, item: TokenStream) -> syn::Result<TokenStream> {
+    diagnostic::expand(|| chainlink_attr(attr, item))
+}
+
+/// Create a chainlink from a trait definition.
+fn chainlink_attr(attr: TokenStream, item: TokenStream) -> syn::Result<TokenStream> {
+    let args = syn::parse2::<parse::ChainlinkArgs>(attr)?;
+    let raw = item.clone();
+    let mut item = syn::parse2::<syn::ItemTrait>(item)?;
+
+    let name = item.ident.clone();
+
+    let generics = item.generics.params.iter().filter_map(|param| match param {
+        syn::GenericParam::Lifetime(l) => Some(TypeParamBound::Lifetime(l.lifetime.clone())),
+        syn::GenericParam::Type(t) => {
+            let ident = &t.ident;
+            Some(syn::parse_quote! { #ident })
+        },
+        syn::GenericParam::Const(c) => {
+            emit_error!(c, "Chainlinks cannot have const generics");
+            None
+        }
+    }).collect::<Vec<_>>();
+
+    let fields = trait_fields(&mut item.items);
+
+    abort_if_dirty();
+
+    let meta_fields = fields.iter().map(|field| match field {
+        ChainlinkField::Const(name, ty) => quote! { const #name: #ty; },
+        ChainlinkField::Mut(name, ty) => quote! { mut #name: #ty; },
+        ChainlinkField::Static(name, ty) => quote! { static #name: #ty; },
+        ChainlinkField::Fn(func) => func.to_token_stream(),
+        ChainlinkField::Type(ty) => ty.to_token_stream()
+    });
+
+    let meta = quote! {
+        #name < #( #generics ),* > => {
+            #(#meta_fields)*
+        }
+    };
+
+    let mut bounds = item.generics.params.iter().filter_map(|param| match param {
+        syn::GenericParam::Lifetime(l) if !l.bounds.is_empty() => {
+            let (lifetime, bounds) = (&l.lifetime, &l.bounds);
+            Some(syn::parse_quote! { #lifetime: #bounds })
+        },
+        syn::GenericParam::Type(t) if !t.bounds.is_empty() => {
+            let (ident, bounds) = (&t.ident, &t.bounds);
+            Some(syn::parse_quote! { #ident: #bounds })
+        },
+        _ => None
+    }).collect::<Vec<syn::WherePredicate>>();
+
+    bounds.extend(item.generics.where_clause.iter().flat_map(|w| w.predicates.iter().cloned()));
+
+    let ast = parse::Chainlink {
+        pointers: args.pointers,
+        sealed: args.sealed,
+        name: name.clone(),
+        generics,
+        supertraits: item.supertraits.iter().cloned().collect(),
+        fields
+    };
+
+    let (sealed, kind_fn, support) = chainlink_support(&ast, &item.vis, &bounds, &meta, &raw);
+
+    item.ident = format_ident!("{}Chainlink", name, span = name.span());
+
+    if let Some(sealed) = sealed {
+        item.colon_token.get_or_insert_with(Default::default);
+        item.supertraits.insert(0, syn::parse_quote! { #sealed });
+        item.items.push(syn::parse_quote! { #kind_fn });
+    }
+
+    let expanded = quote! {
+        #item
+
+        #support
+    };
+
+    Ok(expanded)
+}
+
+/// Get the chainlink fields declared by the items of
+/// a `#[chainlink]` trait, removing their `#[field]`
+/// attributes.
+fn trait_fields(items: &mut [syn::TraitItem]) -> Vec<ChainlinkField> {
+    let mut fields = Vec::new();
+
+    for trait_item in items.iter_mut() {
+        match trait_item {
+            syn::TraitItem::Fn(func) => {
+                let mut is_field = false;
+
+                func.attrs.retain(|attr| {
+                    if !attr.path().is_ident("field") {
+                        return true;
+                    }
+
+                    if let Err(err) = attr.meta.require_path_only() {
+                        emit_error!(err.span(), "{}", err);
+                    }
+
+                    is_field = true;
+                    false
+                });
+
+                if is_field {
+                    match field_item(func) {
+                        Some(field) => fields.push(field),
+                        None => emit_error!(
+                            func.sig, "`#[field]` methods must be `fn {}(&self) -> &T`, `fn {}(&mut self) -> &mut T` or `fn {}(&self) -> T`", func.sig.ident, func.sig.ident, func.sig.ident
+                        )
+                    }
+                } else {
+                    // Default bodies are only marked, so forwarding
+                    // impls still call the implementor's override.
+                    let attrs = match func.default {
+                        Some(_) => vec![syn::parse_quote! { #[default] }],
+                        None => vec![]
+                    };
+
+                    fields.push(ChainlinkField::Fn(syn::TraitItemFn {
+                        attrs,
+                        sig: func.sig.clone(),
+                        default: None,
+                        semi_token: Some(Default::default())
+                    }));
+                }
+            },
+            syn::TraitItem::Type(ty) => {
+                fields.push(ChainlinkField::Type(syn::TraitItemType {
+                    attrs: vec![],
+                    ..ty.clone()
+                }));
+            },
+            syn::TraitItem::Const(c) => {
+                emit_error!(c, "Chainlinks cannot have associated constants");
+            },
+            other => {
+                emit_error!(other, "Unsupported chainlink item");
+            }
+        }
+    }
+
+    fields
+}
+
+/// Get the chainlink field a `#[field]` method
+/// declares, if its signature is one of a field.
+fn field_item(func: &syn::TraitItemFn) -> Option<ChainlinkField> {
+    let sig = &func.sig;
+
+    if func.default.is_some() || !sig.generics.params.is_empty() || sig.inputs.len() != 1 {
+        return None;
+    }
+
+    let receiver = sig.receiver()?;
+
+    if receiver.reference.is_none() || receiver.colon_token.is_some() {
+        return None;
+    }
+
+    let ty = match &sig.output {
+        syn::ReturnType::Type(_, ty) => &**ty,
+        syn::ReturnType::Default => return None
+    };
+
+    let name = sig.ident.clone();
+
+    match (receiver.mutability, ty) {
+        (Some(_), Type::Reference(r)) if r.mutability.is_some() && r.lifetime.is_none() => Some(ChainlinkField::Mut(name, (*r.elem).clone())),
+        (Some(_), _) => None,
+        (None, Type::Reference(r)) if r.mutability.is_none() && r.lifetime.is_none() => Some(ChainlinkField::Const(name, (*r.elem).clone())),
+        (None, Type::Reference(r)) if r.mutability.is_some() => None,
+        (None, ty) => Some(ChainlinkField::Static(name, ty.clone()))
+    }
+}
+
+/// Whether any of `idents` appear in some tokens.
+fn mentions_any(tokens: impl ToTokens, idents: &[syn::Ident]) -> bool {
+    tokens.into_token_stream().into_iter().any(|tt| match tt {
+        proc_macro2::TokenTree::Ident(ident) => idents.contains(&ident),
+        proc_macro2::TokenTree::Group(group) => mentions_any(group.stream(), idents),
+        _ => false
+    })
+}
+
+/// Finds `Self` used as a type, other than through
+/// an associated type like `Self::Item`.
+struct FindSelf(bool);
+
+impl<'ast> Visit<'ast> for FindSelf {
+    fn visit_type_path(&mut self, ty: &'ast syn::TypePath) {
+        if ty.qself.is_none() && ty.path.is_ident("Self") {
+            self.0 = true;
+        }
+
+        syn::visit::visit_type_path(self, ty);
+    }
+}
+
+/// Whether a signature has a `Self: Sized` bound,
+/// which excludes it from trait objects.
+fn requires_sized(sig: &syn::Signature) -> bool {
+    sig.generics.where_clause.iter().flat_map(|w| w.predicates.iter()).any(|p| match p {
+        syn::WherePredicate::Type(p) => {
+            matches!(&p.bounded_ty, Type::Path(ty) if ty.qself.is_none() && ty.path.is_ident("Self"))
+                && p.bounds.iter().any(|b| matches!(b, TypeParamBound::Trait(t) if t.path.is_ident("Sized")))
+        },
+        _ => false
+    })
+}
+
+/// Get why a chainlink function would make the
+/// chainlink's trait object unusable, if it would.
+fn object_safety_violation(sig: &syn::Signature) -> Option<&'static str> {
+    if sig.asyncness.is_some() {
+        return Some("it is `async`");
+    }
+
+    if sig.receiver().is_none() {
+        return Some("it has no `self` receiver");
+    }
+
+    if sig.generics.type_params().next().is_some() || sig.generics.const_params().next().is_some() {
+        return Some("it has generic type parameters");
+    }
+
+    let args = sig.inputs.iter().filter_map(|arg| match arg {
+        syn::FnArg::Typed(arg) => Some(&*arg.ty),
+        syn::FnArg::Receiver(_) => None
+    }).collect::<Vec<_>>();
+
+    if args.iter().any(|ty| ty.to_token_stream().into_iter().any(|tt| matches!(tt, proc_macro2::TokenTree::Ident(i) if i == "impl"))) {
+        return Some("it takes `impl Trait` arguments");
+    }
+
+    if let syn::ReturnType::Type(_, ty) = &sig.output {
+        if let Type::ImplTrait(_) = **ty {
+            return Some("it returns `impl Trait`");
+        }
+    }
+
+    if uses_self(sig) {
+        return Some("it uses `Self` outside of its receiver");
+    }
+
+    None
+}
+
+/// Whether a signature uses `Self` in its arguments
+/// or return type, so forwarding it to another type
+/// would not type check.
+fn uses_self(sig: &syn::Signature) -> bool {
+    let mut find = FindSelf(false);
+
+    for input in sig.inputs.iter() {
+        if let syn::FnArg::Typed(arg) = input {
+            find.visit_type(&arg.ty);
+        }
+    }
+
+    if let syn::ReturnType::Type(_, ty) = &sig.output {
+        find.visit_type(ty);
+    }
+
+    find.0
+}
+
+/// Report chainlink functions that would make the
+/// chainlink's trait object unusable.
+/// 
+/// Functions bounded by `where Self: Sized` are
+/// excluded from trait objects, so they are allowed.
+fn check_object_safety(ast: &parse::Chainlink) {
+    for field in ast.fields.iter() {
+        let func = match field {
+            ChainlinkField::Fn(func) => func,
+            ChainlinkField::Type(ty) => {
+                if !ty.generics.params.is_empty() {
+                    emit_error!(ty.generics, "Chainlink associated type `{}` cannot be generic, because `dyn {}Chainlink` could not name it", ty.ident, ast.name);
+                }
+
+                continue;
+            },
+            _ => continue
+        };
+
+        if requires_sized(&func.sig) {
+            continue;
+        }
+
+        if let Some(reason) = object_safety_violation(&func.sig) {
+            emit_error!(
+                func.sig.ident, "Chainlink function `{}` cannot be called on `dyn {}Chainlink`, because {}", func.sig.ident, ast.name, reason;
+                help = "add `where Self: Sized` to exclude it from `{}`", ast.name
+            );
+        }
+    }
+}
+
+/// Generate everything a chainlink needs besides
+/// its trait.
+/// 
+/// This is the sealing module, the trait object
+/// aliases, the pointer implementations and the
+/// metadata macro, which expands to `meta`. Also
+/// returns the sealing supertrait and the `kind`
+/// method to add to the trait, if it is sealed.
+fn chainlink_support(ast: &parse::Chainlink, vis: &Visibility, bounds: &[syn::WherePredicate], meta: &TokenStream, raw: &TokenStream) -> (Option<TokenStream>, TokenStream, TokenStream) {
+    let name = &ast.name;
+    let trait_name = format_ident!("{}Chainlink", name);
+    let send_name = format_ident!("Send{}", name);
+    let sync_name = format_ident!("Sync{}", name);
+
+    check_object_safety(ast);
+
+    let mut supertrait = None;
+    let mut sealed = quote! {};
+    let mut kind_fn = quote! {};
+
+    if let Some(implementors) = &ast.sealed {
+        if !ast.generics.is_empty() {
+            emit_error!(name, "Sealed chainlinks cannot be generic");
+        }
+
+        if ast.pointers {
+            emit_error!(name, "Sealed chainlinks cannot have pointer implementations");
+        }
+
+        let mut variants = Vec::<(syn::Ident, &Path)>::new();
+
+        for path in implementors {
+            let variant = path.segments.last().unwrap().ident.clone();
+
+            match variants.iter().find(|(first, _)| *first == variant) {
+                Some((first, _)) => emit_error!(
+                    path, "Duplicate implementor `{}`", variant;
+                    note = first => "first given here"
+                ),
+                None => variants.push((variant, path))
+            }
+        }
+
+        let kind_name = format_ident!("{}Kind", name);
+        let module = format_ident!("__typechain_sealed_{}", name);
+        let message = format!("`{{Self}}` is not listed as an implementor of sealed chainlink `{}`", name);
+        let note = format!("only the chains listed after `sealed {} =` can implement it", name);
+
+        let decls = variants.iter().map(|(variant, path)| quote! { #variant(&'a #path) });
+        let impls = variants.iter().map(|(variant, path)| {
+            quote! {
+                impl #module::Sealed for #path {
+                    fn __typechain_kind(&self) -> #kind_name<'_> {
+                        #kind_name::#variant(self)
+                    }
+                }
+            }
+        });
+
+        supertrait = Some(quote! { #module::Sealed });
+
+        // The sealing module does not import its parent,
+        // so the paths to implementors are only resolved
+        // from the module of the chainlink.
+        sealed = quote! {
+            #[allow(missing_docs)]
+            #vis enum #kind_name<'a> {
+                #(#decls),*
+            }
+
+            #[doc(hidden)]
+            #[allow(non_snake_case)]
+            mod #module {
+                #[diagnostic::on_unimplemented(message = #message, note = #note)]
+                pub trait Sealed {
+                    fn __typechain_kind(&self) -> super::#kind_name<'_>;
+                }
+            }
+
+            #(#impls)*
+        };
+
+        kind_fn = quote! {
+            fn kind(&self) -> #kind_name<'_> {
+                #module::Sealed::__typechain_kind(self)
+            }
+        };
+    }
+
+    abort_if_dirty();
+
+    // Trait objects must name every associated type,
+    // so the aliases take them as extra parameters.
+    let generics = &ast.generics;
+    let assoc = ast.fields.iter().filter_map(|f| match f {
+        ChainlinkField::Type(ty) => Some(&ty.ident),
+        _ => None
+    }).collect::<Vec<_>>();
+
+    let alias_generics = quote! { < #( #generics, )* #( #assoc ),* > };
+    let object = quote! { dyn #trait_name < #( #generics, )* #( #assoc = #assoc ),* > };
+
+    let meta_name = meta_ident("chainlink", name, raw);
+
+    let pointers = if ast.pointers {
+        pointer_impls(ast, &trait_name, bounds)
+    } else {
+        quote! {}
+    };
+
+    let schema_fields = ast.fields.iter().filter_map(|f| match f {
+        ChainlinkField::Const(name, ty) => Some(schema_field(name, "Const", ty)),
+        ChainlinkField::Mut(name, ty) => Some(schema_field(name, "Mut", ty)),
+        ChainlinkField::Static(name, ty) => Some(schema_field(name, "Static", ty)),
+        ChainlinkField::Fn(_) | ChainlinkField::Type(_) => None
+    }).collect::<Vec<_>>();
+
+    let schema = schema_const(name, "Chainlink", &quote! { < #( #generics ),* > }, &schema_fields, &[], &[]);
+    let schema_where = match bounds {
+        [] => quote! {},
+        bounds => quote! { where #( #bounds ),* }
+    };
+
+    let support = quote! {
+        #sealed
+
+        #[allow(missing_docs)]
+        #vis type #name #alias_generics = #object;
+
+        impl #alias_generics ::typechain::Described for #object #schema_where {
+            #schema
+        }
+
+        #[allow(missing_docs)]
+        #vis type #send_name #alias_generics = #object + Send;
+
+        #[allow(missing_docs)]
+        #vis type #sync_name #alias_generics = #object + Send + Sync;
+
+        #pointers
+
+        #[doc(hidden)]
+        #[macro_export]
+        macro_rules! #meta_name {
+            ($callback:path { $($args:tt)* }) => {
+                $callback! { { $($args)* } #meta }
+            };
+        }
+
+        #[doc(hidden)]
+        #vis use #meta_name as #name;
+    };
+
+    (supertrait, kind_fn, support)
+}
+
+/// Expand a `chain!` invocation.
+pub fn expand_chain(input: TokenStream) -> syn::Result<TokenStream> {
+    diagnostic::expand(|| {
+        let raw = input.clone();
+        let ast = syn::parse2::<parse::Chain>(input)?;
+
+        Ok(chain(&ast, &raw))
+    })
+}
+
+/// Create a chain.
+fn chain(ast: &parse::Chain, raw: &TokenStream) -> TokenStream {
+    let name = ast.name.clone();
+    let generics = ast.generics.clone();
+
+    let generics = quote! {
+        < #( #generics ),* >
+    };
+
+    if let Some(variants) = &ast.variants {
+        return expand_enum_chain(&name, &generics, variants, raw);
+    }
+
+    if !matches!(ast.shape, ChainShape::Named) {
+        return expand_tuple_chain(ast, &generics, raw);
+    }
+
+    let fields = ast.fields.iter().filter_map(|f| {
+        match f.field.clone() {
+            ChainFieldData::Const(vis, name, ty) => {
+                Some(quote! {
+                    #vis #name: #ty
+                })
+            },
+            ChainFieldData::Mut(name, ty) => {
+                Some(quote! {
+                    #name: #ty
+                })
+            },
+            ChainFieldData::Static(..) => {
+                None
+            },
+            ChainFieldData::Delegate(vis, name, ty) | ChainFieldData::Flatten(vis, name, ty) => {
+                Some(quote! {
+                    #vis #name: #ty
+                })
+            }
+        }
+    });
+
+    let impls = named_chain_impls(&name, &generics, &generics, &quote! {}, &ast.fields, raw);
+
+    quote! {
+        pub struct #name #generics {
+            #(#fields),*
+        }
+
+        #impls
+    }
+}
+
+/// Generate the chainlink implementations and the
+/// metadata macro for a chain with named fields.
+fn named_chain_impls(name: &syn::Ident, impl_generics: &TokenStream, ty_generics: &TokenStream, where_clause: &TokenStream, fields: &[parse::ChainField], raw: &TokenStream) -> TokenStream {
+    let parents = fields.iter().fold(Vec::<Path>::new(), |mut parents, f| {
+        for parent in f.parents.iter() {
+            if !parents.contains(parent) {
+                parents.push(parent.clone());
+            }
+        }
+
+        parents
+    });
+
+    let flattened = fields.iter().filter_map(|f| {
+        match f.field.clone() {
+            ChainFieldData::Flatten(_, field, ty) => Some((field, ty)),
+            _ => None
+        }
+    }).collect::<Vec<_>>();
+
+    let flattened_types = flattened.iter().map(|(_, ty)| ty.clone()).collect::<Vec<_>>();
+
+    let delegates = fields.iter().flat_map(|f| {
+        match f.field.clone() {
+            ChainFieldData::Delegate(_, field, ty) => {
+                f.parents.iter().map(|p| (p.clone(), field.clone(), ty.clone())).collect()
+            },
+            _ => vec![]
+        }
+    }).collect::<Vec<_>>();
+
+    let mut trait_funcs = Vec::new();
+
+    for f in fields.iter() {
+        if let ChainFieldData::Delegate(..) | ChainFieldData::Flatten(..) = f.field {
+            continue;
+        }
+
+        for parent in f.parents.iter() {
+            if delegates.iter().any(|(p, ..)| p == parent) {
+                emit_error!(parent, "Chainlink `{}` is already delegated to a field", parent.to_token_stream());
+                continue;
+            }
+
+            let tokens = match f.field.clone() {
+                ChainFieldData::Const(vis, name, ty) => {
+                    if !matches!(vis, Visibility::Inherited) {
+                        emit_error!(vis, "Chainlink fields must be of inherited visibility");
+                    }
+
+                    quote! { const #name: #ty = self.#name; }
+                },
+                ChainFieldData::Mut(name, ty) => quote! { mut #name: #ty = self.#name; },
+                // Statics given by their chainlink alone take
+                // the item and its type from the chainlink.
+                ChainFieldData::Static(name, Type::Infer(_), expr) => quote! { static #name = #expr; },
+                ChainFieldData::Static(name, ty, expr) => quote! { static #name: #ty = #expr; },
+                ChainFieldData::Delegate(..) | ChainFieldData::Flatten(..) => unreachable!()
+            };
+
+            abort_if_dirty();
+
+            trait_funcs.push((parent.clone(), tokens));
+        }
+    }
+
+    let trait_funcs = group_by_chain(trait_funcs);
+
+    // Items are implemented from the chainlink's metadata,
+    // so their types can be checked against it.
+    let trait_impls = trait_funcs.iter().map(|(parent, items)| {
+        let meta = meta_path(parent);
+
+        quote! {
+            #meta! { ::typechain::__impl_chainlink {
+                impl #impl_generics #parent for #name #ty_generics #where_clause;
+                chain {
+                    #(#items)*
+                }
+            } }
+        }
+    });
+
+    let delegate_impls = delegates.iter().map(|(parent, field, ty)| {
+        let meta = meta_path(parent);
+
+        quote! {
+            #meta! { ::typechain::__impl_chainlink {
+                impl #impl_generics #parent for #name #ty_generics #where_clause;
+                delegate #field: #ty
+            } }
+        }
+    });
+
+    let flatten_impls = flattened.iter().map(|(field, ty)| {
+        let chain = match ty {
+            syn::Type::Path(path) if path.qself.is_none() => path.path.clone(),
+            _ => {
+                emit_error!(ty, "Flattened fields must be chains");
+                return quote! {};
+            }
+        };
+
+        let meta = meta_path(&chain);
+
+        quote! {
+            #meta! { ::typechain::__flatten_chain {
+                impl #impl_generics #chain for #name #ty_generics #where_clause;
+                delegate #field: #ty
+            } }
+        }
+    }).collect::<Vec<_>>();
+
+    let meta = chain_meta_macro(name, ty_generics, raw, &parents, &flattened_types);
+    let schema = schema_const(name, "Chain", ty_generics, &schema_chain_fields(fields), &[], &parents);
+
+    quote! {
+        impl #impl_generics ::typechain::Described for #name #ty_generics #where_clause {
+            #schema
+        }
+
+        #(#trait_impls)*
+
+        #(#delegate_impls)*
+
+        #(#flatten_impls)*
+
+        #meta
+    }
+}
+
+/// Expand a `#[chain]` attribute, given its
+/// arguments and the struct it is on.
+pub fn expand_chain_attr(attr: TokenStream, item: TokenStream) -> syn::Result<TokenStream> {
+    diagnostic::expand(|| chain_attr(attr, item))
+}
+
+/// Create a chain from a struct definition.
+fn chain_attr(attr: TokenStream, item: TokenStream) -> syn::Result<TokenStream> {
+    if !attr.is_empty() {
+        emit_error!(attr, "`#[chain]` does not take arguments");
+    }
+
+    let raw = item.clone();
+    let mut item = syn::parse2::<syn::ItemStruct>(item)?;
+
+    let fields = attribute_fields("link", &item.ident, &item.attrs, &item.fields);
+
+    let is_link = |attr: &syn::Attribute| attr.path().is_ident("link") || attr.path().is_ident("flatten");
+
+    item.attrs.retain(|attr| !is_link(attr));
+
+    for field in item.fields.iter_mut() {
+        field.attrs.retain(|attr| !is_link(attr));
+    }
+
+    let (impl_generics, ty_generics, where_clause) = item.generics.split_for_impl();
+
+    let impls = named_chain_impls(
+        &item.ident,
+        &impl_generics.to_token_stream(),
+        &ty_generics.to_token_stream(),
+        &where_clause.to_token_stream(),
+        &fields,
+        &raw
+    );
+
+    let expanded = quote! {
+        #item
+
+        #impls
+    };
+
+    Ok(expanded)
+}
+
+/// Expand a `#[derive(Chain)]`, given the struct it
+/// is on.
+pub fn expand_derive_chain(input: TokenStream) -> syn::Result<TokenStream> {
+    diagnostic::expand(|| derive_chain(input))
+}
+
+/// Derive the chainlinks of a struct.
+fn derive_chain(input: TokenStream) -> syn::Result<TokenStream> {
+    let raw = input.clone();
+    let item = syn::parse2::<syn::DeriveInput>(input)?;
+
+    let data = match &item.data {
+        syn::Data::Struct(data) => data,
+        _ => abort!(item.ident, "`Chain` can only be derived for structs")
+    };
+
+    let fields = attribute_fields("chain", &item.ident, &item.attrs, &data.fields);
+
+    let (impl_generics, ty_generics, where_clause) = item.generics.split_for_impl();
+
+    Ok(named_chain_impls(
+        &item.ident,
+        &impl_generics.to_token_stream(),
+        &ty_generics.to_token_stream(),
+        &where_clause.to_token_stream(),
+        &fields,
+        &raw
+    ))
+}
+
+/// Read the chain fields of a struct from its
+/// `#[attr_name(...)]` attributes, and from bare
+/// `#[flatten]` attributes with `#[chain]`.
+/// 
+/// Derives can only see their registered helper, so
+/// they flatten with `#[chain(flatten)]` instead.
+fn attribute_fields(attr_name: &str, name: &syn::Ident, attrs: &[syn::Attribute], fields: &syn::Fields) -> Vec<parse::ChainField> {
+    let mut chain_fields = Vec::new();
+
+    for attr in attrs.iter().filter(|a| a.path().is_ident(attr_name)) {
+        match attr.parse_args::<parse::LinkStatic>() {
+            Ok(parse::LinkStatic(field @ parse::ChainField { field: ChainFieldData::Static(..), .. })) => chain_fields.push(field),
+            Ok(_) => emit_error!(attr, "Only `static` items can be linked on the struct"),
+            Err(err) => emit_error!(err.span(), "{}", err)
+        }
+    }
+
+    let named = match fields {
+        syn::Fields::Named(named) => named,
+        _ => abort!(name, "Attribute chains must have named fields")
+    };
+
+    for field in named.named.iter() {
+        let mut links: Option<parse::LinkArgs> = None;
+
+        for attr in field.attrs.iter() {
+            let args = if attr_name == "link" && attr.path().is_ident("flatten") {
+                attr.meta.require_path_only().map(|_| parse::LinkArgs {
+                    kind: parse::LinkKind::Flatten,
+                    parents: vec![]
+                })
+            } else if attr.path().is_ident(attr_name) {
+                attr.parse_args::<parse::LinkArgs>()
+            } else {
+                continue;
+            };
+
+            match args {
+                Ok(args) => match &mut links {
+                    Some(links) if links.kind != args.kind => {
+                        emit_error!(attr, "All links on a field must be of the same kind");
+                    },
+                    Some(links) => links.parents.extend(args.parents),
+                    None => links = Some(args)
+                },
+                Err(err) => emit_error!(err.span(), "{}", err)
+            }
+        }
+
+        let Some(links) = links else {
+            continue;
+        };
+
+        let vis = field.vis.clone();
+        let name = field.ident.clone().unwrap();
+        let ty = field.ty.clone();
+
+        let data = match links.kind {
+            parse::LinkKind::Const => ChainFieldData::Const(vis, name, ty),
+            parse::LinkKind::Mut => {
+                if !matches!(vis, Visibility::Inherited) {
+                    emit_error!(vis, "Chainlink fields must be of inherited visibility");
+                }
+
+                ChainFieldData::Mut(name, ty)
+            },
+            parse::LinkKind::Delegate => ChainFieldData::Delegate(vis, name, ty),
+            parse::LinkKind::Flatten => ChainFieldData::Flatten(vis, name, ty)
+        };
+
+        chain_fields.push(parse::ChainField {
+            parents: links.parents,
+            field: data
+        });
+    }
+
+    parse::check_chain_fields(&chain_fields);
+
+    abort_if_dirty();
+
+    chain_fields
+}
+
+/// Expand a tuple or unit chain.
+/// 
+/// Tuple fields are unnamed, so every chainlink is
+/// implemented from its metadata, matching the fields
+/// to items by position and kind.
+fn expand_tuple_chain(ast: &parse::Chain, generics: &TokenStream, raw: &TokenStream) -> TokenStream {
+    let name = &ast.name;
+
+    let tuple_fields = match &ast.shape {
+        ChainShape::Tuple(fields) => fields.clone(),
+        _ => vec![]
+    };
+
+    let mut parents = Vec::<Path>::new();
+
+    for parent in tuple_fields.iter().flat_map(|f| f.parents.iter()).chain(ast.fields.iter().flat_map(|f| f.parents.iter())) {
+        if !parents.contains(parent) {
+            parents.push(parent.clone());
+        }
+    }
+
+    let impls = parents.iter().map(|parent| {
+        let tuple_items = tuple_fields.iter().enumerate().filter(|(_, f)| f.parents.contains(parent)).map(|(i, f)| {
+            let index = syn::Index::from(i);
+
+            shorthand(&parse::ImplField {
+                kind: f.kind,
+                name: syn::Ident::new("_", Span::call_site()),
+                ty: Some(f.ty.clone()),
+                expr: syn::parse_quote! { self.#index },
+                chain: None
+            })
+        });
+
+        let static_items = ast.fields.iter().filter(|f| f.parents.contains(parent)).map(|f| {
+            match &f.field {
+                ChainFieldData::Static(name, _, expr) => quote! { static #name = #expr; },
+                _ => unreachable!()
+            }
+        });
+
+        let meta = meta_path(parent);
+
+        quote! {
+            #meta! { ::typechain::__impl_chainlink {
+                impl #generics #parent for #name #generics;
+                tuple {
+                    #(#tuple_items)*
+                    #(#static_items)*
+                }
+            } }
+        }
+    }).collect::<Vec<_>>();
+
+    let decl = match &ast.shape {
+        ChainShape::Tuple(_) => {
+            let fields = tuple_fields.iter().map(|f| {
+                let vis = &f.vis;
+                let ty = &f.ty;
+
+                quote! { #vis #ty }
+            });
+
+            quote! {
+                pub struct #name #generics(#(#fields),*);
+            }
+        },
+        _ => quote! {
+            pub struct #name #generics;
+        }
+    };
+
+    let meta = chain_meta_macro(name, generics, raw, &parents, &[]);
+
+    let mut schema_fields = tuple_fields.iter().enumerate().map(|(i, f)| {
+        schema_field(i, if f.kind == ImplFieldKind::Mut { "Mut" } else { "Const" }, &f.ty)
+    }).collect::<Vec<_>>();
+
+    schema_fields.extend(schema_chain_fields(&ast.fields));
+
+    let schema = schema_const(name, "Chain", generics, &schema_fields, &[], &parents);
+
+    quote! {
+        #decl
+
+        impl #generics ::typechain::Described for #name #generics {
+            #schema
+        }
+
+        #(#impls)*
+
+        #meta
+    }
+}
+
+/// Expand a chain enum, implementing each chainlink
+/// by matching on the variants.
+fn expand_enum_chain(name: &syn::Ident, generics: &TokenStream, variants: &[parse::ChainVariant], raw: &TokenStream) -> TokenStream {
+    let decls = variants.iter().map(|v| {
+        let variant = &v.name;
+
+        let fields = v.fields.iter().filter_map(|f| {
+            match &f.field {
+                ChainFieldData::Const(vis, name, ty) => {
+                    if !matches!(vis, Visibility::Inherited) {
+                        emit_error!(vis, "Enum variant fields cannot have a visibility");
+                    }
+
+                    Some(quote! { #name: #ty })
+                },
+                ChainFieldData::Mut(name, ty) => Some(quote! { #name: #ty }),
+                ChainFieldData::Static(..) => None,
+                ChainFieldData::Delegate(_, name, _) | ChainFieldData::Flatten(_, name, _) => {
+                    emit_error!(name, "Enum variants cannot delegate or flatten fields");
+                    None
+                }
+            }
+        }).collect::<Vec<_>>();
+
+        quote! {
+            #variant { #(#fields),* }
+        }
+    }).collect::<Vec<_>>();
+
+    let parents = variants.iter().flat_map(|v| v.fields.iter()).fold(Vec::<Path>::new(), |mut parents, f| {
+        for parent in f.parents.iter() {
+            if !parents.contains(parent) {
+                parents.push(parent.clone());
+            }
+        }
+
+        parents
+    });
+
+    // Items are implemented from the chainlink's metadata,
+    // so their kinds and types come from the chainlink.
+    let trait_impls = parents.iter().map(|parent| {
+        let variant_items = variants.iter().map(|v| {
+            let variant = &v.name;
+
+            let items = v.fields.iter().filter(|f| f.parents.contains(parent)).map(|f| {
+                match &f.field {
+                    ChainFieldData::Const(_, name, ty) => quote! { const #name: #ty = #name; },
+                    ChainFieldData::Mut(name, ty) => quote! { mut #name: #ty = #name; },
+                    ChainFieldData::Static(name, ty, expr) => quote! { static #name: #ty = #expr; },
+                    ChainFieldData::Delegate(..) | ChainFieldData::Flatten(..) => quote! {}
+                }
+            });
+
+            quote! {
+                #variant { #(#items)* }
+            }
+        });
+
+        let meta = meta_path(parent);
+
+        quote! {
+            #meta! { ::typechain::__impl_chainlink {
+                impl #generics #parent for #name #generics;
+                variants {
+                    #(#variant_items)*
+                }
+            } }
+        }
+    }).collect::<Vec<_>>();
+
+    abort_if_dirty();
+
+    let meta = chain_meta_macro(name, generics, raw, &parents, &[]);
+
+    let schema_variants = variants.iter().map(|v| {
+        let variant = v.name.to_string();
+        let fields = schema_chain_fields(&v.fields);
+
+        quote! {
+            ::typechain::SchemaVariant::__new(#variant, &[#(#fields),*])
+        }
+    }).collect::<Vec<_>>();
+
+    let schema = schema_const(name, "Enum", generics, &[], &schema_variants, &parents);
+
+    quote! {
+        pub enum #name #generics {
+            #(#decls),*
+        }
+
+        impl #generics ::typechain::Described for #name #generics {
+            #schema
+        }
+
+        #(#trait_impls)*
+
+        #meta
+    }
+}
+
+/// Expand a `chain_enum!` invocation.
+pub fn expand_chain_enum(input: TokenStream) -> syn::Result<TokenStream> {
+    diagnostic::expand(|| chain_enum(input))
+}
+
+/// Create an enum over chains.
+fn chain_enum(input: TokenStream) -> syn::Result<TokenStream> {
+    let raw = input.clone();
+    let ast = syn::parse2::<parse::ChainEnum>(input)?;
+
+    let name = ast.name.clone();
+    let chain = ast.chain.clone();
+    let meta = meta_path(&chain);
+
+    let variants = ast.variants.iter().map(|p| (p.segments.last().unwrap().ident.clone(), p.clone())).collect::<Vec<_>>();
+
+    let decls = variants.iter().map(|(variant, ty)| {
+        quote! {
+            #variant(#ty)
+        }
+    });
+
+    let from_impls = variants.iter().map(|(variant, ty)| {
+        quote! {
+            impl From<#ty> for #name {
+                fn from(value: #ty) -> Self {
+                    Self::#variant(value)
+                }
+            }
+        }
+    });
+
+    let match_variants = variants.iter().map(|(variant, ty)| {
+        quote! { #variant: #ty }
+    });
+
+    let dyn_arms = variants.iter().map(|(variant, _)| {
+        quote! {
+            Self::#variant(value) => value
+        }
+    }).collect::<Vec<_>>();
+
+    let chain_meta = chain_meta_macro(&name, &quote! {}, &raw, std::slice::from_ref(&chain), &[]);
+
+    let expanded = quote! {
+        #[allow(missing_docs)]
+        pub enum #name {
+            #(#decls),*
+        }
+
+        #(#from_impls)*
+
+        #meta! { ::typechain::__impl_chainlink {
+            impl #chain for #name;
+            match #(#match_variants),*
+        } }
+
+        impl #name {
+            /// Get the value as a chainlink object.
+            pub fn as_dyn(&self) -> &#chain {
+                match self {
+                    #(#dyn_arms),*
+                }
+            }
+
+            /// Get the value as a mutable chainlink object.
+            pub fn as_dyn_mut(&mut self) -> &mut #chain {
+                match self {
+                    #(#dyn_arms),*
+                }
+            }
+        }
+
+        #chain_meta
+    };
+
+    Ok(expanded)
+}
+
+/// Expand a `use_chains!` invocation.
+pub fn expand_use_chains(input: TokenStream) -> syn::Result<TokenStream> {
+    diagnostic::expand(|| use_chains(input))
+}
+
+/// Import chainlink traits.
+fn use_chains(input: TokenStream) -> syn::Result<TokenStream> {
+    let paths = syn::parse2::<parse::UseChains>(input)?;
+
+    // The `Send` and `Sync` aliases are left out, so
+    // they do not clash with types of the same name.
+    let paths = paths.0.iter().map(|p| {
+        let path = chainlink_trait(p);
+
+        quote! {
+            #[allow(unused_imports)]
+            use #path;
+            #[allow(unused_imports)]
+            use #p;
+        }
+    }).collect::<Vec<_>>();
+
+    let expanded = quote! {
+        #(#paths)*
+    };
+
+    Ok(expanded)
+}
+
+/// Expand an `impl_chains!` invocation.
+pub fn expand_impl_chains(input: TokenStream) -> syn::Result<TokenStream> {
+    diagnostic::expand(|| Ok(impl_chains(syn::parse2(input)?)))
+}
+
+/// Manually implement chains.
+fn impl_chains(ast: parse::ImplChains) -> TokenStream {
+    let ty = ast.ty.clone();
+    let generics = ast.generics.clone();
+    let (impl_generics, _, where_clause) = generics.split_for_impl();
+
+    let impls_paths = ast.impls.iter().map(|i| i.chain.clone()).collect::<Vec<_>>();
+
+    let mut impls = group_by_chain(ast.impls.into_iter().map(|i| (i.chain, i.func.to_token_stream())));
+    let shorthands = group_by_chain(ast.fields.iter().map(|f| (f.chain.clone().unwrap(), shorthand(f))));
+
+    // Chainlinks with shorthand fields need their types,
+    // so the whole impl is generated from the metadata.
+    let shorthands = shorthands.into_iter().map(|(chain, fields)| {
+        let meta = meta_path(&chain);
+        let funcs = match impls.iter().position(|(c, _)| *c == chain) {
+            Some(index) => impls.remove(index).1,
+            None => vec![]
+        };
+
+        quote! {
+            #meta! { ::typechain::__impl_chainlink {
+                impl #impl_generics #chain for #ty #where_clause;
+                {
+                    #(#fields)*
+                    #(#funcs)*
+                }
+            } }
+        }
+    }).collect::<Vec<_>>();
+
+    let impls = impls.iter().map(|(trait_, tokens)| {
+        let trait_ = chainlink_trait(trait_);
+
+        let tokens = tokens.clone();
+
+        quote! {
+            impl #impl_generics #trait_ for #ty #where_clause {
+                #(#tokens)*
+            }
+        }
+    }).collect::<Vec<_>>();
+
+    let delegates = ast.delegates.iter().map(|d| {
+        let meta = meta_path(&d.chain);
+        let chain = d.chain.clone();
+        let member = d.member.clone();
+        let field_ty = d.ty.clone();
+
+        if impls_paths.contains(&chain) {
+            emit_error!(chain, "Chainlink `{}` is already delegated to a field", chain.to_token_stream());
+        }
+
+        quote! {
+            #meta! { ::typechain::__impl_chainlink {
+                impl #impl_generics #chain for #ty #where_clause;
+                delegate #member: #field_ty
+            } }
+        }
+    }).collect::<Vec<_>>();
+
+    let derefs = ast.derefs.iter().flat_map(|d| {
+        d.chains.iter().map(|chain| (d.expr.clone(), chain.clone()))
+    }).map(|(expr, chain)| {
+        let meta = meta_path(&chain);
+
+        quote! {
+            #meta! { ::typechain::__impl_chainlink {
+                impl #impl_generics #chain for #ty #where_clause;
+                deref #expr
+            } }
+        }
+    }).collect::<Vec<_>>();
+
+    // Proc macros cannot emit warnings on stable, so
+    // the deprecation is reported by using a deprecated item.
+    let deprecated_where = ast.deprecated_where.map(|where_token| {
+        quote_spanned! { where_token.span =>
+            const _: () = {
+                #[deprecated(note = "use `impl_chains!(impl<...> Type => ...)` instead of `where <...>`")]
+                #[allow(non_camel_case_types)]
+                struct where_generics;
+
+                let _ = where_generics;
+            };
+        }
+    });
+
+    quote! {
+        #deprecated_where
+
+        #(#impls)*
+
+        #(#shorthands)*
+
+        #(#delegates)*
+
+        #(#derefs)*
+    }
+}
+
+/// Expand a `__impl_chainlink!` invocation, which
+/// the metadata macro of a chainlink makes.
+pub fn expand_impl_chainlink(input: TokenStream) -> syn::Result<TokenStream> {
+    diagnostic::expand(|| impl_chainlink(input))
+}
+
+/// Implement a chainlink from its metadata.
+fn impl_chainlink(input: TokenStream) -> syn::Result<TokenStream> {
+    let ast = syn::parse2::<parse::Callback<parse::ImplTarget, parse::ChainlinkMeta>>(input)?;
+
+    let target = ast.args;
+    let mut chainlink = ast.meta.0;
+
+    let mut substitute = Substitute::new(&chainlink.generics, &target.chain);
+
+    for field in chainlink.fields.iter_mut() {
+        substitute.field(field);
+    }
+
+    let trait_ = chainlink_trait(&target.chain);
+    let ty = target.ty;
+    let (impl_generics, _, where_clause) = target.generics.split_for_impl();
+
+    // Forwarded functions return what the target
+    // returns, which is only right if `Self` is not
+    // part of their signature.
+    if !matches!(target.strategy, ImplStrategy::Items(..) | ImplStrategy::Variants(..)) {
+        for field in chainlink.fields.iter() {
+            if let ChainlinkField::Fn(func) = field {
+                if uses_self(&func.sig) {
+                    emit_error!(
+                        target.chain, "Chainlink function `{}` uses `Self` outside of its receiver, so `{}` cannot be forwarded", func.sig.ident, chainlink.name;
+                        help = "implement `{}Chainlink` by hand instead", chainlink.name
+                    );
+                }
+            }
+        }
+    }
+
+    let funcs = match target.strategy {
+        ImplStrategy::Delegate(member, field_ty) => {
+            let access = match member {
+                Member::Named(ident) => quote! { self.#ident },
+                Member::Unnamed(index) => quote! { self.#index }
+            };
+
+            chainlink.fields.iter().map(|f| forward_field(f, &trait_, &field_ty, &access)).collect::<Vec<_>>()
+        },
+        ImplStrategy::Deref(expr) => {
+            let access = respan_self(expr.to_token_stream());
+            let infer = syn::parse_quote! { _ };
+
+            chainlink.fields.iter().map(|f| forward_field(f, &trait_, &infer, &access)).collect::<Vec<_>>()
+        },
+        ImplStrategy::Match(variants) => {
+            chainlink.fields.iter().map(|f| forward_match(f, &trait_, &variants)).collect::<Vec<_>>()
+        },
+        ImplStrategy::Items(source, fields, funcs) => {
+            let mut used = fields.iter().filter(|f| f.name != "_").map(|f| f.name.clone()).collect::<Vec<_>>();
+
+            let mut tokens = fields.into_iter().filter_map(|field| {
+                let expr = respan_self(field.expr.to_token_stream());
+
+                let item = if field.name == "_" {
+                    // Unnamed fields take the first item of
+                    // their kind left, and are checked against
+                    // its type like any other field.
+                    let item = chainlink.fields.iter().find(|f| {
+                        matches!(
+                            (field.kind, f),
+                            (ImplFieldKind::Const, ChainlinkField::Const(..)) | (ImplFieldKind::Mut, ChainlinkField::Mut(..)) | (ImplFieldKind::Static, ChainlinkField::Static(..))
+                        ) && !used.contains(f.name())
+                    });
+
+                    match item {
+                        Some(item) => {
+                            used.push(item.name().clone());
+                            Some(item)
+                        },
+                        None => {
+                            let kind = match field.kind {
+                                ImplFieldKind::Const => "const",
+                                ImplFieldKind::Mut => "mut",
+                                ImplFieldKind::Static => "static"
+                            };
+
+                            match &field.ty {
+                                Some(field_ty) => emit_error!(field_ty, "Chainlink `{}` has no `{}` item left for this field", chainlink.name, kind),
+                                None => emit_error!(field.name, "Chainlink `{}` has no `{}` item left for this field", chainlink.name, kind)
+                            }
+
+                            return None;
+                        }
+                    }
+                } else {
+                    chainlink.fields.iter().find(|f| *f.name() == field.name)
+                };
+
+                let name = field.name;
+
+                // Items given with a type are checked against
+                // the type the chainlink declares.
+                let declared = field.ty;
+                let checked = |value: TokenStream, method: &str, ty: &Type| match (&declared, item) {
+                    (Some(declared), Some(item)) if declared.to_token_stream().to_string() != ty.to_token_stream().to_string() => {
+                        checked_field(value, method, declared, ty, item.name(), &chainlink.name)
+                    },
+                    _ => value
+                };
+
+                match (field.kind, item) {
+                    (ImplFieldKind::Const, Some(ChainlinkField::Const(name, ty))) => {
+                        let value = checked(quote! { &#expr }, "__typechain_ref", ty);
+
+                        Some(quote! {
+                            fn #name(&self) -> & #ty {
+                                #value
+                            }
+                        })
+                    },
+                    (ImplFieldKind::Mut, Some(ChainlinkField::Mut(name, ty))) => {
+                        let value = checked(quote! { &mut #expr }, "__typechain_mut", ty);
+
+                        Some(quote! {
+                            fn #name(&mut self) -> &mut #ty {
+                                #value
+                            }
+                        })
+                    },
+                    (ImplFieldKind::Static, Some(ChainlinkField::Static(name, ty))) => {
+                        let value = checked(expr, "__typechain_value", ty);
+
+                        Some(quote! {
+                            #[allow(clippy::needless_borrow)]
+                            fn #name(&self) -> #ty {
+                                #value
+                            }
+                        })
+                    },
+                    (_, Some(_)) => {
+                        emit_error!(name, "Chainlink `{}` declares `{}` as a different kind of item", chainlink.name, name);
+                        None
+                    },
+                    (_, None) => {
+                        emit_error!(name, "Chainlink `{}` has no item named `{}`", chainlink.name, name);
+                        None
+                    }
+                }
+            }).collect::<Vec<_>>();
+
+            used.extend(funcs.iter().map(|f| f.sig.ident.clone()));
+
+            let missing = chainlink.fields.iter().filter(|f| !used.contains(f.name()) && !f.has_default()).collect::<Vec<_>>();
+
+            if !missing.is_empty() {
+                missing_items(&missing, source, &target.chain, &chainlink.name, None);
+            }
+
+            tokens.extend(funcs.into_iter().map(|f| f.to_token_stream()));
+
+            tokens
+        },
+        ImplStrategy::Variants(variants) => {
+            let params = target.generics.type_params().map(|t| t.ident.clone()).collect::<Vec<_>>();
+
+            for (variant, fields) in variants.iter() {
+                for field in fields.iter().filter(|field| !chainlink.fields.iter().any(|f| *f.name() == field.name)) {
+                    emit_error!(field.name, "Chainlink `{}` has no item named `{}`", chainlink.name, field.name);
+                }
+
+                let missing = chainlink.fields.iter().filter(|f| {
+                    matches!(f, ChainlinkField::Const(..) | ChainlinkField::Mut(..) | ChainlinkField::Static(..))
+                        && !fields.iter().any(|field| field.name == *f.name())
+                }).collect::<Vec<_>>();
+
+                if !missing.is_empty() {
+                    missing_items(&missing, ImplSource::Chain, &target.chain, &chainlink.name, Some(variant));
+                }
+            }
+
+            // Variants only provide fields, so functions
+            // and associated types are missing from all
+            // of them at once.
+            let missing = chainlink.fields.iter().filter(|f| {
+                matches!(f, ChainlinkField::Fn(..) | ChainlinkField::Type(..)) && !f.has_default()
+            }).collect::<Vec<_>>();
+
+            if !missing.is_empty() {
+                missing_items(&missing, ImplSource::Chain, &target.chain, &chainlink.name, None);
+            }
+
+            chainlink.fields.iter().filter_map(|item| {
+                let arms = variants.iter().filter_map(|(variant, fields)| {
+                    let field = fields.iter().find(|f| f.name == *item.name())?;
+                    let name = &field.name;
+                    let expr = &field.expr;
+
+                    let checked = |value: TokenStream, method: &str, ty: &Type| match &field.ty {
+                        Some(declared) if declared.to_token_stream().to_string() != ty.to_token_stream().to_string() => {
+                            checked_field(value, method, declared, ty, name, &chainlink.name)
+                        },
+                        _ => value
+                    };
+
+                    match (field.kind, item) {
+                        (ImplFieldKind::Const, ChainlinkField::Const(_, ty)) => {
+                            let value = checked(quote! { #name }, "__typechain_ref", ty);
+
+                            Some(quote! { Self::#variant { #name, .. } => #value })
+                        },
+                        (ImplFieldKind::Mut, ChainlinkField::Mut(_, ty)) => {
+                            let value = checked(quote! { #name }, "__typechain_mut", ty);
+
+                            Some(quote! { Self::#variant { #name, .. } => #value })
+                        },
+                        // A static in a variant of a `const` item is
+                        // evaluated once and kept in a static, so it
+                        // lives long enough to be borrowed.
+                        (ImplFieldKind::Static, ChainlinkField::Const(_, ty)) => {
+                            if mentions_any(ty, &params) {
+                                emit_error!(field.ty, "`static` items providing `const` items cannot use the generics of the enum");
+                            }
+
+                            let value = checked(expr.to_token_stream(), "__typechain_value", ty);
+
+                            Some(quote! {
+                                Self::#variant { .. } => {
+                                    static VALUE: ::std::sync::OnceLock<#ty> = ::std::sync::OnceLock::new();
+                                    VALUE.get_or_init(|| #value)
+                                }
+                            })
+                        },
+                        (ImplFieldKind::Static, ChainlinkField::Static(_, ty)) => {
+                            let value = checked(expr.to_token_stream(), "__typechain_value", ty);
+
+                            Some(quote! { Self::#variant { .. } => #value })
+                        },
+                        (_, ChainlinkField::Mut(..)) => {
+                            emit_error!(name, "`{}` must be `mut` in every variant", name);
+                            None
+                        },
+                        _ => {
+                            emit_error!(name, "Chainlink `{}` declares `{}` as a different kind of item", chainlink.name, name);
+                            None
+                        }
+                    }
+                }).collect::<Vec<_>>();
+
+                let sig = match item {
+                    ChainlinkField::Const(name, ty) => quote! { fn #name(&self) -> & #ty },
+                    ChainlinkField::Mut(name, ty) => quote! { fn #name(&mut self) -> &mut #ty },
+                    ChainlinkField::Static(name, ty) => quote! { fn #name(&self) -> #ty },
+                    ChainlinkField::Fn(_) | ChainlinkField::Type(_) => return None
+                };
+
+                Some(quote! {
+                    #[allow(clippy::needless_borrow)]
+                    #sig {
+                        match self {
+                            #(#arms),*
+                        }
+                    }
+                })
+            }).collect::<Vec<_>>()
+        }
+    };
+
+    abort_if_dirty();
+
+    let expanded = quote! {
+        impl #impl_generics #trait_ for #ty #where_clause {
+            #(#funcs)*
+        }
+    };
+
+    Ok(expanded)
+}
+
+/// Render tokens the way they would be written.
+fn display(tokens: impl ToTokens) -> String {
+    let mut text = tokens.to_token_stream().to_string();
+
+    for (from, to) in [(" :: ", "::"), (":: ", "::"), (" < ", "<"), (" <", "<"), ("< ", "<"), (" >", ">"), (" ,", ","), ("& ", "&"), (" (", "("), ("( ", "("), (" )", ")")] {
+        text = text.replace(from, to);
+    }
+
+    text
+}
+
+/// Report the items of a chainlink that an
+/// implementation does not provide, suggesting how
+/// to add each of them. Items missing from one
+/// variant of a chain enum are reported on it.
+fn missing_items(missing: &[&ChainlinkField], source: ImplSource, chain: &Path, chainlink: &syn::Ident, variant: Option<&syn::Ident>) {
+    let parent = display(chain);
+    let names = missing.iter().map(|f| format!("`{}`", f.name())).collect::<Vec<_>>().join(", ");
+
+    let mut diagnostic = match variant {
+        Some(variant) => Diagnostic::spanned(
+            variant.span().span_range(),
+            format!("not all items of chainlink `{}` are implemented by variant `{}`, missing: {}", chainlink, variant, names)
+        ),
+        None => Diagnostic::spanned(
+            chain.span().span_range(),
+            format!("not all items of chainlink `{}` are implemented, missing: {}", chainlink, names)
+        )
+    };
+
+    for field in missing {
+        let (kind, name, ty) = match field {
+            ChainlinkField::Const(name, ty) => ("const", name, ty),
+            ChainlinkField::Mut(name, ty) => ("mut", name, ty),
+            ChainlinkField::Static(name, ty) => ("static", name, ty),
+            ChainlinkField::Fn(func) => {
+                let sig = display(&func.sig);
+
+                diagnostic = diagnostic.note(match source {
+                    ImplSource::ImplChains => format!("add `{} {{ ... }} in {};`", sig, parent),
+                    ImplSource::Chain | ImplSource::Tuple => format!("`{}` cannot be implemented by a chain, implement `{}` with `impl_chains!` instead", sig, parent)
+                });
+
+                continue;
+            },
+            ChainlinkField::Type(ty) => {
+                diagnostic = diagnostic.note(format!("associated type `{}` must be implemented by hand", ty.ident));
+
+                continue;
+            }
+        };
+
+        let ty = display(ty);
+
+        diagnostic = diagnostic.note(match (source, kind) {
+            (ImplSource::ImplChains, "static") => format!("add `static {} = ... in {};`", name, parent),
+            (ImplSource::ImplChains, _) => format!("add `{} {} = self.{} in {};`", kind, name, name, parent),
+            (ImplSource::Chain, "static") => format!("add `@{} static {}: {} = ...;`", parent, name, ty),
+            (ImplSource::Chain, _) => format!("add `@{} {} {}: {};`", parent, kind, name, ty),
+            (ImplSource::Tuple, "static") => format!("add `@{} static {}: {} = ...;` after the fields", parent, name, ty),
+            (ImplSource::Tuple, _) => format!("add the field `@{} {} {}`", parent, kind, ty)
+        });
+    }
+
+    diagnostic.emit();
+}
+
+/// Convert `value` from the type an implementor
+/// declares for a chainlink item to the type the
+/// chainlink declares, with an error spanned on the
+/// declared type if they differ.
+/// 
+/// The types are only known to differ by their
+/// tokens, so the conversion is left to a trait that
+/// only exists between identical types.
+fn checked_field(value: TokenStream, method: &str, declared: &Type, expected: &Type, item: &syn::Ident, chainlink: &syn::Ident) -> TokenStream {
+    let message = format!("field `{}` is `{{Self}}` but chainlink `{}` declares `{{T}}`", item, chainlink);
+    let method = format_ident!("{}", method);
+
+    quote_spanned! { declared.span() =>
+        {
+            #[diagnostic::on_unimplemented(message = #message, label = "expected `{T}`")]
+            trait __TypechainField<T: ?Sized> {
+                fn __typechain_ref(&self) -> &T;
+                fn __typechain_mut(&mut self) -> &mut T;
+                fn __typechain_value(self) -> T where Self: Sized, T: Sized;
+            }
+
+            impl<T: ?Sized> __TypechainField<T> for T {
+                fn __typechain_ref(&self) -> &T {
+                    self
+                }
+
+                fn __typechain_mut(&mut self) -> &mut T {
+                    self
+                }
+
+                fn __typechain_value(self) -> T where T: Sized {
+                    self
+                }
+            }
+
+            <#declared as __TypechainField<#expected>>::#method(#value)
+        }
+    }
+}
+
+/// Expand a `__flatten_chain!` invocation, which
+/// the metadata macro of a chain makes.
+pub fn expand_flatten_chain(input: TokenStream) -> syn::Result<TokenStream> {
+    diagnostic::expand(|| flatten_chain(input))
+}
+
+/// Implement the chainlinks of a flattened chain.
+fn flatten_chain(input: TokenStream) -> syn::Result<TokenStream> {
+    let ast = syn::parse2::<parse::Callback<parse::ImplTarget, TokenStream>>(input)?;
+
+    let target = ast.args;
+
+    // Chainlinks have metadata macros too, so flattening
+    // one reaches this callback with chainlink metadata.
+    let meta = match syn::parse2::<parse::ChainMeta>(ast.meta) {
+        Ok(meta) => meta,
+        Err(_) => abort!(target.chain, "`{}` is not a chain, so it cannot be flattened", target.chain.to_token_stream())
+    };
+
+    let generics = target.generics.clone();
+    let where_clause = target.generics.where_clause.clone();
+    let ty = target.ty.clone();
+
+    let (member, field_ty) = match target.strategy {
+        ImplStrategy::Delegate(member, field_ty) => (member, field_ty),
+        ImplStrategy::Deref(expr) => abort!(expr, "Flattened chains must be delegated to a field"),
+        ImplStrategy::Match(..) | ImplStrategy::Items(..) | ImplStrategy::Variants(..) => abort!(target.chain, "Flattened chains must be delegated to a field")
+    };
+
+    let mut substitute = Substitute::new(&meta.generics, &target.chain);
+
+    let parents = meta.parents.into_iter().map(|mut parent| {
+        substitute.visit_path_mut(&mut parent);
+
+        let meta = meta_path(&parent);
+
+        quote! {
+            #meta! { ::typechain::__impl_chainlink {
+                impl #generics #parent for #ty #where_clause;
+                delegate #member: #field_ty
+            } }
+        }
+    }).collect::<Vec<_>>();
+
+    let flattened = meta.flattened.into_iter().map(|mut inner| {
+        substitute.visit_type_mut(&mut inner);
+
+        let chain = match &inner {
+            syn::Type::Path(path) if path.qself.is_none() => path.path.clone(),
+            _ => {
+                emit_error!(inner, "Flattened fields must be chains");
+                return quote! {};
+            }
+        };
+
+        let meta = meta_path(&chain);
+
+        quote! {
+            #meta! { ::typechain::__flatten_chain {
+                impl #generics #chain for #ty #where_clause;
+                delegate #member: #field_ty
+            } }
+        }
+    }).collect::<Vec<_>>();
+
+    abort_if_dirty();
+
+    let expanded = quote! {
+        #(#parents)*
+
+        #(#flattened)*
+    };
+
+    Ok(expanded)
+}
+
@@ -0,0 +1,1299 @@
+use proc_macro2::Ident;
+use crate::diagnostic::emit_error;
+use quote::ToTokens;
+use syn::{Type, parse::{Parse, ParseStream}, Token, spanned::Spanned, Path, Visibility, Generics, TypeParamBound, Expr, Member, WhereClause};
+
+
+mod kw {
+    syn::custom_keyword!(delegate);
+    syn::custom_keyword!(deref);
+    syn::custom_keyword!(sealed);
+    syn::custom_keyword!(pointers);
+    syn::custom_keyword!(flatten);
+    syn::custom_keyword!(chain);
+    syn::custom_keyword!(tuple);
+    syn::custom_keyword!(variants);
+}
+
+
+fn parse_generics(input: ParseStream) -> syn::Result<Vec<TypeParamBound>> {
+    let lookahead = input.lookahead1();
+
+    if lookahead.peek(Token![<]) {
+        let mut generics = Vec::new();
+
+        input.parse::<Token![<]>()?;
+
+        while !input.peek(Token![>]) {
+            generics.push(input.parse::<TypeParamBound>()?);
+
+            let lookahead = input.lookahead1();
+
+            if lookahead.peek(Token![,]) {
+                input.parse::<Token![,]>()?;
+            }
+        }
+
+        input.parse::<Token![>]>()?;
+
+        Ok(generics)
+    } else {
+        Ok(vec![])
+    }
+}
+
+
+/// Parse the implementors of a sealed chainlink,
+/// as in `Circle | shapes::Square`.
+fn parse_implementors(input: ParseStream) -> syn::Result<Vec<Path>> {
+    let mut implementors = vec![input.parse::<Path>()?];
+
+    while input.peek(Token![|]) {
+        input.parse::<Token![|]>()?;
+        implementors.push(input.parse::<Path>()?);
+    }
+
+    Ok(implementors)
+}
+
+/// The arguments of a `#[chainlink(...)]` attribute.
+#[derive(Clone, Default)]
+pub struct ChainlinkArgs {
+    pub pointers: bool,
+    pub sealed: Option<Vec<Path>>
+}
+
+impl Parse for ChainlinkArgs {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let mut args = ChainlinkArgs::default();
+
+        while !input.is_empty() {
+            let lookahead = input.lookahead1();
+
+            if lookahead.peek(kw::pointers) {
+                input.parse::<kw::pointers>()?;
+
+                args.pointers = true;
+            } else if lookahead.peek(kw::sealed) {
+                input.parse::<kw::sealed>()?;
+                input.parse::<Token![=]>()?;
+
+                args.sealed = Some(parse_implementors(input)?);
+            } else {
+                return Err(lookahead.error());
+            }
+
+            if !input.is_empty() {
+                input.parse::<Token![,]>()?;
+            }
+        }
+
+        Ok(args)
+    }
+}
+
+#[derive(Clone)]
+pub struct Chainlink {
+    pub pointers: bool,
+    pub sealed: Option<Vec<Path>>,
+    pub name: Ident,
+    pub generics: Vec<TypeParamBound>,
+    pub supertraits: Vec<TypeParamBound>,
+    pub fields: Vec<ChainlinkField>,
+}
+
+impl Parse for Chainlink {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        Chainlink::parse_with(input, ChainlinkField::parse)
+    }
+}
+
+/// A chainlink as recorded by its metadata macro.
+/// 
+/// Unlike `chainlink!` input, its functions may be
+/// marked `#[default]`.
+#[derive(Clone)]
+pub struct ChainlinkMeta(pub Chainlink);
+
+impl Parse for ChainlinkMeta {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        Chainlink::parse_with(input, ChainlinkField::parse_meta).map(ChainlinkMeta)
+    }
+}
+
+impl Chainlink {
+    fn parse_with(input: ParseStream, field: fn(ParseStream) -> syn::Result<ChainlinkField>) -> syn::Result<Self> {
+        let mut pointers = false;
+
+        for attr in input.call(syn::Attribute::parse_outer)? {
+            if !attr.path().is_ident("pointers") {
+                return Err(syn::Error::new(attr.span(), "Unsupported chainlink attribute"));
+            }
+
+            attr.meta.require_path_only()?;
+
+            pointers = true;
+        }
+
+        let is_sealed = input.peek(kw::sealed) && input.peek2(syn::Ident);
+
+        if is_sealed {
+            input.parse::<kw::sealed>()?;
+        }
+
+        let name = input.parse::<Ident>()?;
+
+        let generics = parse_generics(input)?;
+
+        let mut supertraits = Vec::new();
+
+        if input.peek(Token![:]) {
+            input.parse::<Token![:]>()?;
+
+            supertraits.push(input.parse::<TypeParamBound>()?);
+
+            while input.peek(Token![+]) {
+                input.parse::<Token![+]>()?;
+                supertraits.push(input.parse::<TypeParamBound>()?);
+            }
+        }
+
+        let sealed = if is_sealed {
+            input.parse::<Token![=]>()?;
+
+            Some(parse_implementors(input)?)
+        } else {
+            None
+        };
+
+        input.parse::<syn::Token![=>]>()?;
+
+        let braced_input;
+        syn::braced!(braced_input in input);
+
+        let fields = {
+            let mut fields = Vec::new();
+
+            while !braced_input.is_empty() {
+                fields.push(braced_input.call(field)?);
+
+                let lookahead = braced_input.lookahead1();
+
+                if lookahead.peek(Token![;]) {
+                    braced_input.parse::<Token![;]>()?;
+                }
+            }
+
+            fields
+        };
+
+        Ok(Chainlink {
+            pointers,
+            sealed,
+            name,
+            generics,
+            supertraits,
+            fields
+        })
+    }
+}
+
+#[derive(Clone)]
+pub enum ChainlinkField {
+    Const(Ident, Type),
+    Mut(Ident, Type),
+    Static(Ident, Type),
+    Fn(syn::TraitItemFn),
+    Type(syn::TraitItemType)
+}
+
+impl ChainlinkField {
+    pub fn name(&self) -> &Ident {
+        match self {
+            ChainlinkField::Const(name, _) | ChainlinkField::Mut(name, _) | ChainlinkField::Static(name, _) => name,
+            ChainlinkField::Fn(func) => &func.sig.ident,
+            ChainlinkField::Type(ty) => &ty.ident
+        }
+    }
+
+    /// Whether the item has a default, so implementors
+    /// do not need to provide it.
+    /// 
+    /// Functions with default bodies are marked with
+    /// `#[default]` in chainlink metadata.
+    pub fn has_default(&self) -> bool {
+        match self {
+            ChainlinkField::Fn(func) => func.attrs.iter().any(|a| a.path().is_ident("default")),
+            _ => false
+        }
+    }
+}
+
+impl Parse for ChainlinkField {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        ChainlinkField::parse_item(input, false)
+    }
+}
+
+impl ChainlinkField {
+    /// Parse an item of chainlink metadata, where
+    /// functions with defaults are marked `#[default]`.
+    pub fn parse_meta(input: ParseStream) -> syn::Result<Self> {
+        ChainlinkField::parse_item(input, true)
+    }
+
+    fn parse_item(input: ParseStream, meta: bool) -> syn::Result<Self> {
+        let lookahead = input.lookahead1();
+
+        if lookahead.peek(Token![const]) {
+            input.parse::<Token![const]>()?;
+
+            let name = input.parse::<Ident>()?;
+
+            input.parse::<Token![:]>()?;
+
+            let ty = input.parse::<Type>()?;
+
+            Ok(ChainlinkField::Const(name, ty))
+        } else if lookahead.peek(Token![mut]) {
+            input.parse::<Token![mut]>()?;
+
+            let name = input.parse::<Ident>()?;
+
+            input.parse::<Token![:]>()?;
+
+            let ty = input.parse::<Type>()?;
+
+            Ok(ChainlinkField::Mut(name, ty))
+        } else if lookahead.peek(Token![static]) {
+            input.parse::<Token![static]>()?;
+
+            let name = input.parse::<Ident>()?;
+
+            input.parse::<Token![:]>()?;
+
+            let ty = input.parse::<Type>()?;
+
+            Ok(ChainlinkField::Static(name, ty))
+        } else if lookahead.peek(Token![fn]) || input.peek(Token![#]) {
+            let func = input.parse::<syn::TraitItemFn>()?;
+
+            for attr in func.attrs.iter() {
+                if !attr.path().is_ident("default") {
+                    return Err(syn::Error::new(attr.span(), "Unsupported chainlink item attribute"));
+                }
+
+                if !meta {
+                    return Err(syn::Error::new(attr.span(), "Functions with defaults can only be declared with `#[chainlink]` on a trait"));
+                }
+
+                attr.meta.require_path_only()?;
+            }
+
+            if let Some(default) = &func.default {
+                return Err(syn::Error::new(default.span(), "Chains cannot have default functions"));
+            }
+
+            Ok(ChainlinkField::Fn(func))
+        } else if lookahead.peek(Token![type]) {
+            let ty = input.parse::<syn::TraitItemType>()?;
+
+            if let Some((eq, _)) = &ty.default {
+                return Err(syn::Error::new(eq.span(), "Chainlink associated types cannot have defaults"));
+            }
+
+            Ok(ChainlinkField::Type(ty))
+        } else {
+            Err(lookahead.error())
+        }
+    }
+}
+
+#[derive(Clone)]
+pub struct Chain {
+    pub name: Ident,
+    pub generics: Vec<TypeParamBound>,
+    pub fields: Vec<ChainField>,
+    pub variants: Option<Vec<ChainVariant>>,
+    pub shape: ChainShape
+}
+
+#[derive(Clone)]
+pub enum ChainShape {
+    Named,
+    Tuple(Vec<ChainTupleField>),
+    Unit
+}
+
+impl Parse for Chain {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let is_enum = input.peek(Token![enum]);
+
+        if is_enum {
+            input.parse::<Token![enum]>()?;
+        }
+
+        let name = input.parse::<Ident>()?;
+
+        let generics = parse_generics(input)?;
+
+        if !is_enum && !input.peek(Token![=>]) {
+            let shape = if input.peek(syn::token::Paren) {
+                let paren_input;
+                syn::parenthesized!(paren_input in input);
+
+                let fields = paren_input.parse_terminated(ChainTupleField::parse, Token![,])?;
+
+                ChainShape::Tuple(fields.into_iter().collect())
+            } else {
+                ChainShape::Unit
+            };
+
+            // Static items follow the struct after a `;`.
+            let fields = if input.peek(Token![;]) {
+                input.parse::<Token![;]>()?;
+
+                parse_chain_fields(input)?
+            } else {
+                vec![]
+            };
+
+            for field in fields.iter() {
+                if !matches!(field.field, ChainFieldData::Static(..)) {
+                    return Err(syn::Error::new(input.span(), "Tuple and unit chains can only have `static` items after the struct"));
+                }
+            }
+
+            return Ok(Chain {
+                name,
+                generics,
+                fields,
+                variants: None,
+                shape
+            });
+        }
+
+        input.parse::<syn::Token![=>]>()?;
+
+        let braced_input;
+        syn::braced!(braced_input in input);
+
+        if is_enum {
+            let variants = braced_input.parse_terminated(ChainVariant::parse, Token![,])?;
+
+            return Ok(Chain {
+                name,
+                generics,
+                fields: vec![],
+                variants: Some(variants.into_iter().collect()),
+                shape: ChainShape::Named
+            });
+        }
+
+        let fields = parse_chain_fields(&braced_input)?;
+
+        Ok(Chain {
+            name,
+            generics,
+            fields,
+            variants: None,
+            shape: ChainShape::Named
+        })
+    }
+}
+
+fn parse_chain_fields(input: ParseStream) -> syn::Result<Vec<ChainField>> {
+    let mut fields = Vec::new();
+
+    while !input.is_empty() {
+        fields.push(input.parse::<ChainField>()?);
+
+        let lookahead = input.lookahead1();
+
+        if lookahead.peek(Token![;]) {
+            input.parse::<Token![;]>()?;
+        }
+    }
+
+    check_chain_fields(&fields);
+
+    Ok(fields)
+}
+
+/// Report parents that are given more than once.
+/// 
+/// Returns the parents without duplicates.
+fn check_parents(parents: &[Path]) -> Vec<&Path> {
+    let mut unique: Vec<&Path> = Vec::new();
+
+    for parent in parents {
+        match unique.iter().find(|p| **p == parent) {
+            Some(first) => emit_error!(
+                parent, "Duplicate chainlink `{}`", parent.to_token_stream();
+                note = first => "first given here"
+            ),
+            None => unique.push(parent)
+        }
+    }
+
+    unique
+}
+
+/// Report duplicate field names, duplicate parents,
+/// fields implementing the same chainlink item and
+/// delegate fields without chainlinks.
+/// 
+/// Parsing continues after these errors, so every
+/// duplicate in a chain is reported at once.
+pub fn check_chain_fields(fields: &[ChainField]) {
+    let mut names: Vec<&Ident> = Vec::new();
+    let mut items: Vec<(&Path, &Ident)> = Vec::new();
+
+    for field in fields {
+        let name = field.field.name();
+
+        if !matches!(field.field, ChainFieldData::Static(..)) {
+            match names.iter().find(|n| **n == name) {
+                Some(first) => emit_error!(
+                    name, "Duplicate field `{}`", name;
+                    note = first => "first defined here"
+                ),
+                None => names.push(name)
+            }
+        }
+
+        if let ChainFieldData::Delegate(..) = field.field {
+            if field.parents.is_empty() {
+                emit_error!(name, "Delegate field `{}` does not implement any chainlinks", name);
+            }
+        }
+
+        if let ChainFieldData::Delegate(..) | ChainFieldData::Flatten(..) = field.field {
+            check_parents(&field.parents);
+            continue;
+        }
+
+        // Items named `_` are only matched to the
+        // chainlink once its metadata is read.
+        if name == "_" {
+            continue;
+        }
+
+        for parent in check_parents(&field.parents) {
+            match items.iter().find(|(p, n)| *p == parent && *n == name) {
+                Some((_, first)) => emit_error!(
+                    name, "Chainlink item `{}` of `{}` is already implemented by another field", name, parent.to_token_stream();
+                    note = first => "first implemented here"
+                ),
+                None => items.push((parent, name))
+            }
+        }
+    }
+}
+
+#[derive(Clone)]
+pub struct ChainTupleField {
+    pub parents: Vec<Path>,
+    pub vis: Visibility,
+    pub kind: ImplFieldKind,
+    pub ty: Type
+}
+
+impl Parse for ChainTupleField {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let mut parents = Vec::new();
+
+        while input.peek(Token![@]) {
+            input.parse::<Token![@]>()?;
+            parents.push(input.parse::<Path>()?);
+        }
+
+        check_parents(&parents);
+
+        let vis = input.parse::<Visibility>()?;
+        let kind = input.parse::<ImplFieldKind>()?;
+
+        match kind {
+            ImplFieldKind::Static => return Err(syn::Error::new(input.span(), "Tuple fields cannot be `static`")),
+            ImplFieldKind::Mut if vis != Visibility::Inherited => {
+                return Err(syn::Error::new(vis.span(), "Chainlink fields must be of inherited visibility"));
+            },
+            ImplFieldKind::Const if vis != Visibility::Inherited && !parents.is_empty() => {
+                return Err(syn::Error::new(vis.span(), "Chainlink fields must be of inherited visibility"));
+            },
+            _ => {}
+        }
+
+        let ty = input.parse::<Type>()?;
+
+        Ok(ChainTupleField {
+            parents,
+            vis,
+            kind,
+            ty
+        })
+    }
+}
+
+#[derive(Clone)]
+pub struct ChainVariant {
+    pub name: Ident,
+    pub fields: Vec<ChainField>
+}
+
+impl Parse for ChainVariant {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let name = input.parse::<Ident>()?;
+
+        let fields = if input.peek(syn::token::Brace) {
+            let braced_input;
+            syn::braced!(braced_input in input);
+
+            parse_chain_fields(&braced_input)?
+        } else {
+            vec![]
+        };
+
+        Ok(ChainVariant {
+            name,
+            fields
+        })
+    }
+}
+
+#[derive(Clone)]
+pub struct ChainField {
+    pub parents: Vec<Path>,
+    pub field: ChainFieldData
+}
+
+impl Parse for ChainField {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let attrs = input.call(syn::Attribute::parse_outer)?;
+
+        for attr in attrs.iter() {
+            if !attr.path().is_ident("flatten") {
+                return Err(syn::Error::new(attr.span(), "Unsupported chain field attribute"));
+            }
+
+            attr.meta.require_path_only()?;
+        }
+
+        if !attrs.is_empty() {
+            let vis = input.parse::<Visibility>()?;
+            let name = input.parse::<Ident>()?;
+
+            input.parse::<Token![:]>()?;
+
+            let ty = input.parse::<Type>()?;
+
+            return Ok(ChainField {
+                parents: vec![],
+                field: ChainFieldData::Flatten(vis, name, ty)
+            });
+        }
+
+        let mut lookahead = input.lookahead1();
+
+        let mut parents = Vec::new();
+
+        while lookahead.peek(Token![@]) {
+            input.parse::<Token![@]>()?;
+
+            parents.push(input.parse::<Path>()?);
+
+            lookahead = input.lookahead1();
+        }
+
+        let field = input.parse::<ChainFieldData>()?;
+
+        Ok(ChainField {
+            parents,
+            field
+        })
+    }
+}
+
+#[derive(Clone)]
+pub enum ChainFieldData {
+    Const(Visibility, Ident, Type),
+    Mut(Ident, Type),
+    Static(Ident, Type, Expr),
+    Delegate(Visibility, Ident, Type),
+    Flatten(Visibility, Ident, Type)
+}
+
+impl ChainFieldData {
+    pub fn name(&self) -> &Ident {
+        match self {
+            ChainFieldData::Const(_, name, _) | ChainFieldData::Mut(name, _) | ChainFieldData::Static(name, ..) => name,
+            ChainFieldData::Delegate(_, name, _) | ChainFieldData::Flatten(_, name, _) => name
+        }
+    }
+}
+
+impl Parse for ChainFieldData {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let vis = input.parse::<Visibility>()?;
+
+        let lookahead = input.lookahead1();
+
+        if lookahead.peek(Token![const]) {
+            input.parse::<Token![const]>()?;
+
+            let name = input.parse::<Ident>()?;
+
+            input.parse::<Token![:]>()?;
+
+            let ty = input.parse::<Type>()?;
+
+            Ok(ChainFieldData::Const(vis, name, ty))
+        } else if lookahead.peek(Token![mut]) {
+            if vis != Visibility::Inherited {
+                return Err(syn::Error::new(vis.span(), "Chainlink fields must be of inherited visibility"));
+            }
+
+            input.parse::<Token![mut]>()?;
+
+            let name = input.parse::<Ident>()?;
+
+            input.parse::<Token![:]>()?;
+
+            let ty = input.parse::<Type>()?;
+
+            Ok(ChainFieldData::Mut(name, ty))
+        } else if lookahead.peek(Token![static]) {
+            if vis != Visibility::Inherited {
+                return Err(syn::Error::new(vis.span(), "Chainlink fields must be of inherited visibility"));
+            }
+
+            input.parse::<Token![static]>()?;
+
+            let name = input.parse::<Ident>()?;
+
+            input.parse::<Token![:]>()?;
+
+            let ty = input.parse::<Type>()?;
+
+            input.parse::<Token![=]>()?;
+
+            let expr = input.parse::<Expr>()?;
+
+            Ok(ChainFieldData::Static(name, ty, expr))
+        } else if lookahead.peek(kw::delegate) {
+            input.parse::<kw::delegate>()?;
+
+            let name = input.parse::<Ident>()?;
+
+            input.parse::<Token![:]>()?;
+
+            let ty = input.parse::<Type>()?;
+
+            Ok(ChainFieldData::Delegate(vis, name, ty))
+        } else {
+            Err(lookahead.error())
+        }
+    }
+}
+
+#[derive(Clone)]
+pub struct ChainEnum {
+    pub name: Ident,
+    pub variants: Vec<Path>,
+    pub chain: Path
+}
+
+impl Parse for ChainEnum {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let name = input.parse::<Ident>()?;
+
+        input.parse::<Token![=]>()?;
+
+        let mut variants = vec![input.parse::<Path>()?];
+
+        while input.peek(Token![|]) {
+            input.parse::<Token![|]>()?;
+            variants.push(input.parse::<Path>()?);
+        }
+
+        input.parse::<Token![:]>()?;
+
+        let chain = input.parse::<Path>()?;
+
+        Ok(ChainEnum {
+            name,
+            variants,
+            chain
+        })
+    }
+}
+
+#[derive(Clone, Copy, PartialEq)]
+pub enum LinkKind {
+    Const,
+    Mut,
+    Delegate,
+    Flatten
+}
+
+/// A `static` item linked on an attribute chain.
+/// 
+/// Besides the full form of a chain field, it can be
+/// given by its chainlink alone, as in
+/// `static HasTags = vec![]`. This implements the next
+/// `static` item of the chainlink, with the type the
+/// chainlink declares, so it is named `_` and typed
+/// `_` until the chainlink's metadata is read.
+#[derive(Clone)]
+pub struct LinkStatic(pub ChainField);
+
+impl Parse for LinkStatic {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        if !input.peek(Token![static]) {
+            return input.parse::<ChainField>().map(LinkStatic);
+        }
+
+        input.parse::<Token![static]>()?;
+
+        let chainlink = input.parse::<Path>()?;
+
+        input.parse::<Token![=]>()?;
+
+        let expr = input.parse::<Expr>()?;
+
+        let name = Ident::new("_", chainlink.span());
+        let ty = Type::Infer(syn::TypeInfer { underscore_token: Token![_](chainlink.span()) });
+
+        Ok(LinkStatic(ChainField {
+            parents: vec![chainlink],
+            field: ChainFieldData::Static(name, ty, expr)
+        }))
+    }
+}
+
+/// The arguments of a `#[link(...)]` field attribute.
+#[derive(Clone)]
+pub struct LinkArgs {
+    pub kind: LinkKind,
+    pub parents: Vec<Path>
+}
+
+impl Parse for LinkArgs {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let kind = if input.peek(Token![mut]) {
+            input.parse::<Token![mut]>()?;
+            LinkKind::Mut
+        } else if input.peek(kw::delegate) {
+            input.parse::<kw::delegate>()?;
+            LinkKind::Delegate
+        } else if input.peek(kw::flatten) && input.peek2(syn::parse::End) {
+            input.parse::<kw::flatten>()?;
+
+            return Ok(LinkArgs {
+                kind: LinkKind::Flatten,
+                parents: vec![]
+            });
+        } else {
+            LinkKind::Const
+        };
+
+        let parents = input.parse_terminated(Path::parse, Token![,])?.into_iter().collect();
+
+        Ok(LinkArgs {
+            kind,
+            parents
+        })
+    }
+}
+
+#[derive(Clone)]
+pub struct UseChains(pub Vec<Path>);
+
+impl syn::parse::Parse for UseChains {
+    fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
+        let mut paths = Vec::new();
+
+        while !input.is_empty() {
+            paths.push(input.parse::<Path>()?);
+
+            if !input.is_empty() {
+                input.parse::<syn::Token![,]>()?;
+            }
+        }
+
+        Ok(UseChains(paths))
+    }
+}
+
+#[derive(Clone)]
+pub struct ImplChains {
+    pub ty: syn::Type,
+    pub impls: Vec<ImplChain>,
+    pub delegates: Vec<ImplDelegate>,
+    pub derefs: Vec<ImplDeref>,
+    pub fields: Vec<ImplField>,
+    pub generics: Generics,
+    pub deprecated_where: Option<Token![where]>
+}
+
+impl syn::parse::Parse for ImplChains {
+    fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
+        let mut generics = Generics::default();
+
+        if input.peek(Token![impl]) {
+            input.parse::<Token![impl]>()?;
+            generics = input.parse::<Generics>()?;
+        }
+
+        let ty = input.parse::<syn::Type>()?;
+
+        if input.peek(Token![where]) {
+            generics.where_clause = Some(input.parse::<WhereClause>()?);
+        }
+
+        input.parse::<syn::Token![=>]>()?;
+
+        let mut impls = Vec::new();
+        let mut delegates = Vec::new();
+        let mut derefs = Vec::new();
+        let mut fields = Vec::new();
+
+        if input.peek(kw::deref) {
+            derefs.push(input.parse::<ImplDeref>()?);
+        } else {
+            let braced_input;
+            syn::braced!(braced_input in input);
+
+            ImplChains::parse_items(&braced_input, &mut impls, &mut delegates, &mut fields)?;
+        }
+
+        let lookahead = input.lookahead1();
+
+        // `where <T: Bound>` is the old spelling of `impl<T: Bound>`.
+        let deprecated_where = if lookahead.peek(Token![where]) {
+            let where_token = input.parse::<Token![where]>()?;
+            let old_generics = input.parse::<Generics>()?;
+
+            if !generics.params.is_empty() {
+                return Err(syn::Error::new(where_token.span, "Generics cannot be given both with `impl<...>` and with `where <...>`"));
+            }
+
+            generics.params = old_generics.params;
+
+            Some(where_token)
+        } else {
+            None
+        };
+
+        Ok(ImplChains {
+            ty,
+            impls,
+            delegates,
+            derefs,
+            fields,
+            generics,
+            deprecated_where
+        })
+    }
+}
+
+impl ImplChains {
+    fn parse_items(braced_input: ParseStream, impls: &mut Vec<ImplChain>, delegates: &mut Vec<ImplDelegate>, fields: &mut Vec<ImplField>) -> syn::Result<()> {
+        while !braced_input.is_empty() {
+            if braced_input.peek(kw::delegate) {
+                delegates.push(braced_input.parse::<ImplDelegate>()?);
+            } else if ImplFieldKind::peek(braced_input) {
+                let field = braced_input.parse::<ImplField>()?;
+
+                braced_input.parse::<Token![in]>()?;
+
+                fields.push(ImplField {
+                    chain: Some(braced_input.parse::<Path>()?),
+                    ..field
+                });
+            } else {
+                let func = braced_input.parse::<syn::TraitItemFn>()?;
+
+                braced_input.parse::<syn::Token![in]>()?;
+
+                let chain = braced_input.parse::<syn::Path>()?;
+
+                impls.push(ImplChain {
+                    func,
+                    chain
+                });
+            }
+
+            let lookahead = braced_input.lookahead1();
+
+            if lookahead.peek(Token![;]) {
+                braced_input.parse::<Token![;]>()?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[derive(Clone)]
+pub struct ImplChain {
+    pub func: syn::TraitItemFn,
+    pub chain: syn::Path
+}
+
+#[derive(Clone)]
+pub struct ImplDelegate {
+    pub member: Member,
+    pub ty: Type,
+    pub chain: Path
+}
+
+impl Parse for ImplDelegate {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        input.parse::<kw::delegate>()?;
+
+        let member = input.parse::<Member>()?;
+
+        input.parse::<Token![:]>()?;
+
+        let ty = input.parse::<Type>()?;
+
+        input.parse::<Token![in]>()?;
+
+        let chain = input.parse::<Path>()?;
+
+        Ok(ImplDelegate {
+            member,
+            ty,
+            chain
+        })
+    }
+}
+
+#[derive(Clone, Copy, PartialEq)]
+pub enum ImplFieldKind {
+    Const,
+    Mut,
+    Static
+}
+
+impl ImplFieldKind {
+    fn peek(input: ParseStream) -> bool {
+        input.peek(Token![const]) || input.peek(Token![mut]) || input.peek(Token![static])
+    }
+}
+
+impl Parse for ImplFieldKind {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let lookahead = input.lookahead1();
+
+        if lookahead.peek(Token![const]) {
+            input.parse::<Token![const]>()?;
+            Ok(ImplFieldKind::Const)
+        } else if lookahead.peek(Token![mut]) {
+            input.parse::<Token![mut]>()?;
+            Ok(ImplFieldKind::Mut)
+        } else if lookahead.peek(Token![static]) {
+            input.parse::<Token![static]>()?;
+            Ok(ImplFieldKind::Static)
+        } else {
+            Err(lookahead.error())
+        }
+    }
+}
+
+/// A chainlink item implemented by an expression.
+/// 
+/// Items named `_` are matched to the next chainlink
+/// item of the same kind, and checked against the
+/// type given after the name, if any.
+#[derive(Clone)]
+pub struct ImplField {
+    pub kind: ImplFieldKind,
+    pub name: Ident,
+    pub ty: Option<Type>,
+    pub expr: Expr,
+    pub chain: Option<Path>
+}
+
+impl Parse for ImplField {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let kind = input.parse::<ImplFieldKind>()?;
+
+        let (name, ty) = if input.peek(Token![_]) {
+            let underscore = input.parse::<Token![_]>()?;
+
+            let ty = if input.peek(Token![:]) {
+                input.parse::<Token![:]>()?;
+
+                Some(input.parse::<Type>()?)
+            } else {
+                None
+            };
+
+            (Ident::new("_", underscore.span), ty)
+        } else {
+            let name = input.parse::<Ident>()?;
+
+            let ty = if input.peek(Token![:]) {
+                input.parse::<Token![:]>()?;
+
+                Some(input.parse::<Type>()?)
+            } else {
+                None
+            };
+
+            (name, ty)
+        };
+
+        input.parse::<Token![=]>()?;
+
+        let expr = input.parse::<Expr>()?;
+
+        Ok(ImplField {
+            kind,
+            name,
+            ty,
+            expr,
+            chain: None
+        })
+    }
+}
+
+#[derive(Clone)]
+pub struct ImplDeref {
+    pub expr: Expr,
+    pub chains: Vec<Path>
+}
+
+impl Parse for ImplDeref {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        input.parse::<kw::deref>()?;
+
+        let expr = input.parse::<Expr>()?;
+
+        input.parse::<Token![for]>()?;
+
+        let mut chains = vec![input.parse::<Path>()?];
+
+        while input.peek(Token![,]) {
+            input.parse::<Token![,]>()?;
+            chains.push(input.parse::<Path>()?);
+        }
+
+        Ok(ImplDeref {
+            expr,
+            chains
+        })
+    }
+}
+
+/// Arguments passed back to a callback macro by the
+/// metadata macro that `chainlink!` or `chain!` generates.
+#[derive(Clone)]
+pub struct Callback<T, M> {
+    pub args: T,
+    pub meta: M
+}
+
+impl<T: Parse, M: Parse> Parse for Callback<T, M> {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let braced_input;
+        syn::braced!(braced_input in input);
+
+        let args = braced_input.parse::<T>()?;
+        let meta = input.parse::<M>()?;
+
+        Ok(Callback {
+            args,
+            meta
+        })
+    }
+}
+
+/// The chainlinks implemented by a chain, as
+/// recorded by its metadata macro.
+#[derive(Clone)]
+pub struct ChainMeta {
+    pub generics: Vec<TypeParamBound>,
+    pub parents: Vec<Path>,
+    pub flattened: Vec<Type>
+}
+
+impl Parse for ChainMeta {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        input.parse::<Ident>()?;
+
+        let generics = parse_generics(input)?;
+
+        input.parse::<Token![=>]>()?;
+
+        let bracketed_input;
+        syn::bracketed!(bracketed_input in input);
+
+        let parents = bracketed_input.parse_terminated(Path::parse, Token![,])?.into_iter().collect();
+
+        let bracketed_input;
+        syn::bracketed!(bracketed_input in input);
+
+        let flattened = bracketed_input.parse_terminated(Type::parse, Token![,])?.into_iter().collect();
+
+        Ok(ChainMeta {
+            generics,
+            parents,
+            flattened
+        })
+    }
+}
+
+#[derive(Clone)]
+pub struct ImplTarget {
+    pub generics: Generics,
+    pub chain: Path,
+    pub ty: Type,
+    pub strategy: ImplStrategy
+}
+
+impl Parse for ImplTarget {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        input.parse::<Token![impl]>()?;
+
+        let mut generics = input.parse::<Generics>()?;
+        let chain = input.parse::<Path>()?;
+
+        input.parse::<Token![for]>()?;
+
+        let ty = input.parse::<Type>()?;
+
+        generics.where_clause = input.parse::<Option<WhereClause>>()?;
+
+        input.parse::<Token![;]>()?;
+
+        let strategy = input.parse::<ImplStrategy>()?;
+
+        Ok(ImplTarget {
+            generics,
+            chain,
+            ty,
+            strategy
+        })
+    }
+}
+
+#[derive(Clone)]
+pub enum ImplStrategy {
+    Delegate(Member, Type),
+    Deref(Expr),
+    Match(Vec<(Ident, Type)>),
+    Items(ImplSource, Vec<ImplField>, Vec<syn::TraitItemFn>),
+    Variants(Vec<(Ident, Vec<ImplField>)>)
+}
+
+/// The macro an `Items` strategy was generated by,
+/// which decides how missing items are suggested.
+#[derive(Clone, Copy, PartialEq)]
+pub enum ImplSource {
+    ImplChains,
+    Chain,
+    Tuple
+}
+
+impl Parse for ImplStrategy {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let lookahead = input.lookahead1();
+
+        if lookahead.peek(kw::delegate) {
+            input.parse::<kw::delegate>()?;
+
+            let member = input.parse::<Member>()?;
+
+            input.parse::<Token![:]>()?;
+
+            let ty = input.parse::<Type>()?;
+
+            Ok(ImplStrategy::Delegate(member, ty))
+        } else if lookahead.peek(kw::deref) {
+            input.parse::<kw::deref>()?;
+
+            Ok(ImplStrategy::Deref(input.parse::<Expr>()?))
+        } else if lookahead.peek(Token![match]) {
+            input.parse::<Token![match]>()?;
+
+            let mut variants = Vec::new();
+
+            while !input.is_empty() {
+                let variant = input.parse::<Ident>()?;
+
+                input.parse::<Token![:]>()?;
+
+                variants.push((variant, input.parse::<Type>()?));
+
+                if !input.is_empty() {
+                    input.parse::<Token![,]>()?;
+                }
+            }
+
+            Ok(ImplStrategy::Match(variants))
+        } else if lookahead.peek(syn::token::Brace) || lookahead.peek(kw::chain) || lookahead.peek(kw::tuple) {
+            let source = if input.peek(kw::chain) {
+                input.parse::<kw::chain>()?;
+                ImplSource::Chain
+            } else if input.peek(kw::tuple) {
+                input.parse::<kw::tuple>()?;
+                ImplSource::Tuple
+            } else {
+                ImplSource::ImplChains
+            };
+
+            let braced_input;
+            syn::braced!(braced_input in input);
+
+            let mut fields = Vec::new();
+            let mut funcs = Vec::new();
+
+            while !braced_input.is_empty() {
+                if ImplFieldKind::peek(&braced_input) {
+                    fields.push(braced_input.parse::<ImplField>()?);
+                } else {
+                    funcs.push(braced_input.parse::<syn::TraitItemFn>()?);
+                }
+
+                if braced_input.peek(Token![;]) {
+                    braced_input.parse::<Token![;]>()?;
+                }
+            }
+
+            Ok(ImplStrategy::Items(source, fields, funcs))
+        } else if lookahead.peek(kw::variants) {
+            input.parse::<kw::variants>()?;
+
+            let braced_input;
+            syn::braced!(braced_input in input);
+
+            let mut variants = Vec::new();
+
+            while !braced_input.is_empty() {
+                let variant = braced_input.parse::<Ident>()?;
+
+                let fields_input;
+                syn::braced!(fields_input in braced_input);
+
+                let mut fields = Vec::new();
+
+                while !fields_input.is_empty() {
+                    fields.push(fields_input.parse::<ImplField>()?);
+
+                    if fields_input.peek(Token![;]) {
+                        fields_input.parse::<Token![;]>()?;
+                    }
+                }
+
+                variants.push((variant, fields));
+            }
+
+            Ok(ImplStrategy::Variants(variants))
+        } else {
+            Err(lookahead.error())
+        }
+    }
+}
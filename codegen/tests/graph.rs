@@ -0,0 +1,187 @@
+use typechain_codegen::graph::{Graph, Edge, EdgeKind};
+
+
+const SOURCE: &str = r#"
+use typechain::{chainlink, chain, impl_chains};
+
+chainlink!(Person => {
+    const name: String;
+    mut age: u8;
+});
+
+chainlink!(Greeter => {
+    fn greet(&self, greeting: &str) -> String;
+});
+
+chain!(Base => {
+    @Person
+    const name: String;
+
+    @Person
+    mut age: u8;
+});
+
+mod nested {
+    chain!(Employee => {
+        @crate::Greeter delegate person: Base;
+
+        #[flatten]
+        base: Base;
+    });
+}
+
+impl_chains!(Vec<u8> => {
+    fn greet(&self, greeting: &str) -> String {
+        format!("{} {}", greeting, self.len())
+    } in Greeter;
+});
+
+fn unrelated() {
+    let chain = vec![1, 2, 3];
+    println!("{:?}", chain);
+}
+"#;
+
+/// Build the edge from `from` to `to`.
+fn edge(from: &str, to: &str, kind: EdgeKind) -> Edge {
+    Edge { from: from.to_string(), to: to.to_string(), kind }
+}
+
+#[test]
+fn test_scan() {
+    let mut graph = Graph::new();
+    graph.add_source(SOURCE).unwrap();
+
+    let chainlinks = graph.chainlinks.iter().map(|c| (c.name.as_str(), c.items.clone())).collect::<Vec<_>>();
+    let chains = graph.chains.iter().map(|c| (c.name.as_str(), c.fields.clone())).collect::<Vec<_>>();
+
+    assert_eq!(chainlinks, vec![
+        ("Person", vec!["const name: String".to_string(), "mut age: u8".to_string()]),
+        ("Greeter", vec!["fn greet(&self, greeting: &str) -> String".to_string()])
+    ]);
+
+    assert_eq!(chains, vec![
+        ("Base", vec!["name: String".to_string(), "age: u8".to_string()]),
+        ("Employee", vec!["person: Base".to_string(), "base: Base".to_string()]),
+        ("Vec<u8>", vec![])
+    ]);
+
+    assert_eq!(graph.edges, vec![
+        edge("Base", "Person", EdgeKind::Implements),
+        edge("Employee", "Greeter", EdgeKind::Delegates("person".to_string())),
+        edge("Employee", "Base", EdgeKind::Flattens("base".to_string())),
+        edge("Vec<u8>", "Greeter", EdgeKind::Implements)
+    ]);
+}
+
+#[test]
+fn test_dot() {
+    let mut graph = Graph::new();
+    graph.add_source(SOURCE).unwrap();
+
+    assert_eq!(graph.to_dot(), r#"digraph typechain {
+    node [shape=record];
+    "Person" [label="{\<\<chainlink\>\>\nPerson|const name: String\lmut age: u8\l}"];
+    "Greeter" [label="{\<\<chainlink\>\>\nGreeter|fn greet(&self, greeting: &str) -\> String\l}"];
+    "Base" [label="{Base|name: String\lage: u8\l}"];
+    "Employee" [label="{Employee|person: Base\lbase: Base\l}"];
+    "Vec<u8>" [label="{Vec\<u8\>|}"];
+    "Base" -> "Person" [arrowhead=empty];
+    "Employee" -> "Greeter" [arrowhead=empty, style=dashed, label="delegate person"];
+    "Employee" -> "Base" [arrowhead=diamond, label="flatten base"];
+    "Vec<u8>" -> "Greeter" [arrowhead=empty];
+}
+"#);
+}
+
+#[test]
+fn test_mermaid() {
+    let mut graph = Graph::new();
+    graph.add_source(SOURCE).unwrap();
+
+    assert_eq!(graph.to_mermaid(), r#"classDiagram
+    class Person {
+        <<chainlink>>
+        const name: String
+        mut age: u8
+    }
+    class Greeter {
+        <<chainlink>>
+        fn greet(&self, greeting: &str) -> String
+    }
+    class Base {
+        name: String
+        age: u8
+    }
+    class Employee {
+        person: Base
+        base: Base
+    }
+    class `Vec<u8>`
+    Base ..|> Person
+    Employee ..> Greeter : delegate person
+    Employee *-- Base : flatten base
+    `Vec<u8>` ..|> Greeter
+"#);
+}
+
+#[test]
+fn test_scan_attributes() {
+    let mut graph = Graph::new();
+    graph.add_source(r#"
+        #[typechain::chainlink]
+        pub trait Animal {
+            #[field]
+            fn legs(&self) -> &u8;
+
+            fn speak(&self) -> String;
+        }
+
+        #[typechain::chain]
+        pub struct Dog {
+            #[link(Animal)]
+            legs: u8,
+
+            pub name: String
+        }
+
+        #[derive(Debug, Chain)]
+        #[chain(static HasTags = vec!["pet"])]
+        pub struct Pet {
+            #[chain(delegate Animal)]
+            animal: Dog
+        }
+
+        #[derive(Debug)]
+        pub struct Unrelated {
+            field: u8
+        }
+
+        chain_enum!(Pets = Dog | self::Cat : Animal);
+    "#).unwrap();
+
+    let chainlinks = graph.chainlinks.iter().map(|c| (c.name.as_str(), c.items.clone())).collect::<Vec<_>>();
+    let chains = graph.chains.iter().map(|c| (c.name.as_str(), c.fields.clone())).collect::<Vec<_>>();
+
+    assert_eq!(chainlinks, vec![
+        ("Animal", vec!["const legs: u8".to_string(), "fn speak(&self) -> String".to_string()])
+    ]);
+
+    assert_eq!(chains, vec![
+        ("Dog", vec!["legs: u8".to_string(), "name: String".to_string()]),
+        ("Pet", vec!["animal: Dog".to_string()]),
+        ("Pets", vec!["Dog".to_string(), "Cat".to_string()])
+    ]);
+
+    assert_eq!(graph.edges, vec![
+        edge("Dog", "Animal", EdgeKind::Implements),
+        edge("Pet", "HasTags", EdgeKind::Implements),
+        edge("Pet", "Animal", EdgeKind::Delegates("animal".to_string())),
+        edge("Pets", "Animal", EdgeKind::Implements)
+    ]);
+}
+
+#[test]
+fn test_invalid_source() {
+    assert!(Graph::new().add_source("fn {").is_err());
+}
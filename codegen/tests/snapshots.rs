@@ -0,0 +1,106 @@
+use std::{env, fs};
+
+use proc_macro2::TokenStream;
+use quote::ToTokens;
+use typechain_codegen::{expand_chain, expand_impl_chains};
+
+
+/// Render an expansion with one item per line.
+fn render(tokens: TokenStream) -> String {
+    let file = syn::parse2::<syn::File>(tokens).expect("expansion should be valid items");
+
+    file.items.iter().map(|item| format!("{}\n", item.to_token_stream())).collect()
+}
+
+/// Compare an expansion against its snapshot, or
+/// update the snapshot if `TYPECHAIN_BLESS` is set.
+fn assert_snapshot(name: &str, tokens: TokenStream) {
+    let path = std::path::Path::new(env!("CARGO_MANIFEST_DIR")).join("snapshots").join(format!("{}.txt", name));
+    let actual = render(tokens);
+
+    if env::var_os("TYPECHAIN_BLESS").is_some() {
+        fs::write(&path, &actual).unwrap();
+        return;
+    }
+
+    let expected = fs::read_to_string(&path).unwrap_or_default();
+
+    assert!(actual == expected, "expansion of `{}` changed, run with `TYPECHAIN_BLESS=1` to update it:\n{}", name, actual);
+}
+
+const CHAIN: &str = r#"
+    Employee => {
+        @Person @Named
+        const name: String;
+
+        @Person
+        const age: u8;
+
+        @Employed
+        mut salary: u32;
+
+        @Named
+        static title: &'static str = "employee";
+
+        @Registered delegate company: Company;
+
+        #[flatten]
+        base: Base;
+    }
+"#;
+
+const IMPL_CHAINS: &str = r#"
+    Foreign => {
+        const name = self.name in Named;
+
+        fn age(&self) -> &u8 {
+            &self.age
+        } in Person;
+
+        mut salary = self.salary in Employed;
+
+        fn greet(&self) -> String {
+            String::new()
+        } in Named;
+
+        fn name(&self) -> &String {
+            &self.name
+        } in Person;
+
+        delegate company: Company in Registered;
+    }
+"#;
+
+#[test]
+fn test_chain_snapshot() {
+    assert_snapshot("chain", expand_chain(CHAIN.parse().unwrap()).unwrap());
+}
+
+#[test]
+fn test_impl_chains_snapshot() {
+    assert_snapshot("impl_chains", expand_impl_chains(IMPL_CHAINS.parse().unwrap()).unwrap());
+}
+
+#[test]
+fn test_deterministic() {
+    let chains = (0..8).map(|_| expand_chain(CHAIN.parse().unwrap()).unwrap().to_string()).collect::<Vec<_>>();
+    let impls = (0..8).map(|_| expand_impl_chains(IMPL_CHAINS.parse().unwrap()).unwrap().to_string()).collect::<Vec<_>>();
+
+    assert!(chains.windows(2).all(|w| w[0] == w[1]));
+    assert!(impls.windows(2).all(|w| w[0] == w[1]));
+}
+
+#[test]
+fn test_errors() {
+    let err = expand_chain("Employee => { @Person const name: String; @Person const name: String; }".parse().unwrap()).unwrap_err();
+    let messages = err.into_iter().map(|e| e.to_string()).collect::<Vec<_>>();
+
+    assert_eq!(messages, [
+        "Duplicate field `name`",
+        "note: first defined here",
+        "Chainlink item `name` of `Person` is already implemented by another field",
+        "note: first implemented here"
+    ]);
+
+    assert!(expand_impl_chains("Foreign".parse().unwrap()).is_err());
+}
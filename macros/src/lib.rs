@@ -11,211 +11,149 @@
 //! The macros in this crate use user-defined traits
 //! and structs to generate an easy-to-use chain. See
 //! the [`typechain`](https://crates.io/crates/typechain)
-//! crate for more information.
+//! crate for more information. The code generation
+//! itself is in
+//! [`typechain-codegen`](https://crates.io/crates/typechain-codegen).
 
 extern crate proc_macro;
 
-use std::collections::{HashMap, hash_map::Entry};
-
-use parse::{ChainlinkField, ChainFieldData};
 use proc_macro::TokenStream;
-use proc_macro2::Span;
-use proc_macro_error::{proc_macro_error, emit_error, abort_if_dirty};
-use quote::{quote, ToTokens, quote_spanned};
-use syn::{Path, spanned::Spanned, Visibility};
 
-mod parse;
 
+/// Turn an expansion into the output of a macro,
+/// reporting its errors with `compile_error!`.
+fn output(expansion: syn::Result<proc_macro2::TokenStream>) -> TokenStream {
+    expansion.unwrap_or_else(|err| err.to_compile_error()).into()
+}
 
 /// Create a chainlink trait.
 /// 
 /// The trait will be renamed to `{{name}}Chainlink`,
 /// and the original name will be used for the
 /// associated type (dyn `{{name}}Chainlink`).
-#[proc_macro_error]
+/// `Send{{name}}` and `Sync{{name}}` are also
+/// generated for the `Send` and `Send + Sync`
+/// trait objects.
+/// 
+/// Supertraits can be given after the name, as in
+/// `chainlink!(Person: Send + Sync => { ... })`.
+/// 
+/// A `sealed` chainlink lists the only chains that
+/// may implement it, as in
+/// `chainlink!(sealed Shape = Circle | Square => { ... })`.
+/// It gets a `kind` method returning a `{{name}}Kind`
+/// enum that can be matched on exhaustively. Paths
+/// to the chains are resolved from the module of the
+/// chainlink.
+/// 
+/// With a `#[pointers]` attribute, the trait is also
+/// implemented for `&T`, `&mut T`, `Box<T>`, `Rc<T>`
+/// and `Arc<T>` where `T` implements it. Shared
+/// pointers are skipped if any item takes `&mut self`.
 #[proc_macro]
 pub fn chainlink(input: TokenStream) -> TokenStream {
-    let ast = syn::parse_macro_input!(input as parse::Chainlink);
-
-    let name = ast.name.clone();
-    let generics = ast.generics.clone();
-    let generics = quote! {
-        < #( #generics ),* >
-    };
-
-    let fields = ast.fields.iter().map(|f| {
-        match f {
-            ChainlinkField::Const(name, ty) => {
-                quote! {
-                    fn #name(&self) -> & #ty;
-                }
-            },
-            ChainlinkField::Mut(name, ty) => {
-                quote! {
-                    fn #name(&mut self) -> &mut #ty;
-                }
-            },
-            ChainlinkField::Static(name, ty) => {
-                quote! {
-                    fn #name(&self) -> #ty;
-                }
-            },
-            ChainlinkField::Fn(func) => {
-                let name = func.sig.ident.clone();
-                let generics = func.sig.generics.clone();
-                let inputs = func.sig.inputs.clone();
-                let output = func.sig.output.clone();
-                let where_clause = func.sig.generics.where_clause.clone();
-
-                quote! {
-                    #generics
-                    fn #name(#inputs) #output #where_clause;
-                }
-            }
-        }
-    });
-
-    let trait_name = syn::Ident::new(&format!("{}Chainlink", name), Span::call_site());
-
-    let expanded = quote! {
-        #[allow(missing_docs)]
-        pub trait #trait_name #generics {
-            #(#fields)*
-        }
-
-        #[allow(missing_docs)]
-        pub type #name #generics = dyn #trait_name #generics;
-    };
+    output(typechain_codegen::expand_chainlink(input.into()))
+}
 
-    expanded.into()
+/// Create a chainlink from a trait definition.
+/// 
+/// This is the attribute form of [`chainlink!`]. The
+/// trait is renamed to `{{name}}Chainlink`, and the
+/// same aliases and metadata are generated. Methods
+/// marked `#[field]` are chainlink fields, decided
+/// by their signature:
+/// 
+/// - `fn name(&self) -> &T` is a `const` field.
+/// - `fn name(&mut self) -> &mut T` is a `mut` field.
+/// - `fn name(&self) -> T` is a `static` field.
+/// 
+/// Other methods, including those with default
+/// bodies, and associated types are kept as they
+/// are. Chains cannot set associated types, so
+/// chainlinks with them must be implemented by
+/// hand, though they can still be delegated to.
+/// 
+/// The attribute takes `pointers` and
+/// `sealed = A | B` as arguments.
+#[proc_macro_attribute]
+pub fn chainlink_attr(attr: TokenStream, item: TokenStream) -> TokenStream {
+    output(typechain_codegen::expand_chainlink_attr(attr.into(), item.into()))
 }
 
 /// Create a chain.
-#[proc_macro_error]
+/// 
+/// `chain!(Name(@Chainlink const Type))` creates a
+/// tuple struct, where each field implements the
+/// next chainlink item of the same kind, in the
+/// order the chainlink declares them, and
+/// `chain!(Name;)` creates a unit struct. Either can
+/// be followed by `static` items after a `;`.
+/// 
+/// `chain!(enum Name => { ... })` creates an enum
+/// instead, where every variant provides the items
+/// of each chainlink the enum implements. A variant
+/// can provide a `const` item with a `static` one,
+/// which is evaluated once, on first use, so its
+/// type must be `Send + Sync` and cannot use the
+/// generics of the enum.
 #[proc_macro]
 pub fn chain(input: TokenStream) -> TokenStream {
-    let ast = syn::parse_macro_input!(input as parse::Chain);
-
-    let name = ast.name.clone();
-    let generics = ast.generics.clone();
-
-    let generics = quote! {
-        < #( #generics ),* >
-    };
-
-    let fields = ast.fields.iter().filter_map(|f| {
-        match f.field.clone() {
-            ChainFieldData::Const(vis, name, ty) => {
-                Some(quote! {
-                    #vis #name: #ty
-                })
-            },
-            ChainFieldData::Mut(name, ty) => {
-                Some(quote! {
-                    #name: #ty
-                })
-            },
-            ChainFieldData::Static(..) => {
-                None
-            }
-        }
-    });
-
-    let trait_funcs: HashMap<Path, Vec<proc_macro2::TokenStream>> = ast.fields.iter().fold(HashMap::new(), |mut map, f| {
-        let parents = f.parents.clone();
-
-        for parent in parents {
-            if let Entry::Vacant(_) = map.entry(parent.clone()) {
-                map.insert(parent.clone(), vec![]);
-            }
-
-            let tokens = match f.field.clone() {
-                ChainFieldData::Const(vis, name, ty) => {
-                    if !matches!(vis, Visibility::Inherited) {
-                        emit_error!(vis, "Chainlink fields must be of inherited visibility");
-                    }
-
-                    quote! {
-                        fn #name(&self) -> & #ty {
-                            &self.#name
-                        }
-                    }
-                },
-                ChainFieldData::Mut(name, ty) => {
-                    quote! {
-                        fn #name(&mut self) -> &mut #ty {
-                            &mut self.#name
-                        }
-                    }
-                },
-                ChainFieldData::Static(name, ty, expr) => {
-                    quote_spanned! { expr.span() =>
-                        #[allow(clippy::needless_borrow)]
-                        fn #name(&self) -> #ty {
-                            #expr
-                        }
-                    }
-                }
-            };
-
-            abort_if_dirty();
-
-            map.get_mut(&parent).unwrap().push(tokens);
-        }
-
-        map
-    });
-
-    let trait_impls = trait_funcs.iter().map(|(trait_, tokens)| {
-        let mut trait_ = trait_.clone();
-        trait_.segments.last_mut().unwrap().ident = syn::Ident::new(&format!("{}Chainlink", trait_.segments.last().unwrap().ident), trait_.span());
-
-        let tokens = tokens.clone();
-
-        quote! {
-            impl #generics #trait_ for #name #generics {
-                #(#tokens)*
-            }
-        }
-    });
+    output(typechain_codegen::expand_chain(input.into()))
+}
 
-    let expanded = quote! {
-        pub struct #name #generics {
-            #(#fields),*
-        }
+/// Create a chain from a struct definition.
+/// 
+/// This is the attribute form of [`chain!`]. Fields
+/// are linked to chainlinks with `#[link(...)]`:
+/// 
+/// - `#[link(Person, Adult)]` implements `const` items.
+/// - `#[link(mut State<T>)]` implements `mut` items.
+/// - `#[link(delegate Person)]` delegates to the field.
+/// - `#[flatten]` flattens a chain into the struct.
+/// 
+/// `static` items are given on the struct itself, as in
+/// `#[link(@HasTags static tags: Vec<&'static str> = vec![])]`,
+/// or by their chainlink alone, as in
+/// `#[link(static HasTags = vec![])]`, which takes the
+/// next `static` item and its type from the chainlink.
+#[proc_macro_attribute]
+pub fn chain_attr(attr: TokenStream, item: TokenStream) -> TokenStream {
+    output(typechain_codegen::expand_chain_attr(attr.into(), item.into()))
+}
 
-        #(#trait_impls)*
-    };
+/// Derive the chainlinks of a struct.
+/// 
+/// This is the derive form of [`chain!`], with the
+/// same `#[chain(...)]` attributes as `#[link(...)]`
+/// in [`macro@chain_attr`]. Fields are flattened with
+/// `#[chain(flatten)]`.
+#[proc_macro_derive(Chain, attributes(chain))]
+pub fn derive_chain(input: TokenStream) -> TokenStream {
+    output(typechain_codegen::expand_derive_chain(input.into()))
+}
 
-    expanded.into()
+/// Create an enum over chains.
+/// 
+/// `chain_enum!(AnyPerson = Parent | Child : Person)`
+/// creates an enum with a variant for each chain,
+/// which implements the chainlink by matching on
+/// itself instead of through dynamic dispatch.
+/// `From` is implemented for each chain, and
+/// `as_dyn` returns the value as a chainlink object.
+#[proc_macro]
+pub fn chain_enum(input: TokenStream) -> TokenStream {
+    output(typechain_codegen::expand_chain_enum(input.into()))
 }
 
 /// Import chainlink traits.
 /// 
 /// This is a helper macro for importing chainlink
-/// traits and their associated types.
-#[proc_macro_error]
+/// traits and their associated types. The
+/// `Send{{name}}` and `Sync{{name}}` aliases are
+/// not imported, and can be imported with `use`.
 #[proc_macro]
 pub fn use_chains(input: TokenStream) -> TokenStream {
-    let paths = syn::parse_macro_input!(input as parse::UseChains);
-
-    let paths = paths.0.iter().map(|p| {
-        let mut path = p.clone();
-        path.segments.last_mut().unwrap().ident = syn::Ident::new(&format!("{}Chainlink", path.segments.last().unwrap().ident), p.span());
-
-        quote! {
-            #[allow(unused_imports)]
-            use #path;
-            #[allow(unused_imports)]
-            use #p;
-        }
-    }).collect::<Vec<_>>();
-
-    let expanded = quote! {
-        #(#paths)*
-    };
-
-    expanded.into()
+    output(typechain_codegen::expand_use_chains(input.into()))
 }
 
 /// Manually implement chains.
@@ -223,42 +161,33 @@ pub fn use_chains(input: TokenStream) -> TokenStream {
 /// This macro will generate chain implementations
 /// manually. This is useful when you want to implement
 /// chains for a type that you don't own.
-#[proc_macro_error]
+/// 
+/// Generic implementations take the same generics
+/// and `where` clause as an `impl` block, as in
+/// `impl_chains!(impl<T> Vec<T> where T: Copy => { ... })`.
 #[proc_macro]
 pub fn impl_chains(input: TokenStream) -> TokenStream {
-    let ast = syn::parse_macro_input!(input as parse::ImplChains);
-
-    let ty = ast.ty.clone();
-    let where_clause = ast.where_clause.clone();
-
-    let mut impls: HashMap<Path, Vec<proc_macro2::TokenStream>> = HashMap::new();
-
-    for impl_ in ast.impls {
-        let tokens = impl_.func.to_token_stream();
-
-        if let Entry::Vacant(_) = impls.entry(impl_.chain.clone()) {
-            impls.insert(impl_.chain.clone(), vec![]);
-        }
-
-        impls.get_mut(&impl_.chain).unwrap().push(tokens);
-    }
-
-    let impls = impls.iter().map(|(trait_, tokens)| {
-        let mut trait_ = trait_.clone();
-        trait_.segments.last_mut().unwrap().ident = syn::Ident::new(&format!("{}Chainlink", trait_.segments.last().unwrap().ident), trait_.span());
-
-        let tokens = tokens.clone();
-
-        quote! {
-            impl #where_clause #trait_ for #ty {
-                #(#tokens)*
-            }
-        }
-    }).collect::<Vec<_>>();
+    output(typechain_codegen::expand_impl_chains(input.into()))
+}
 
-    let expanded = quote! {
-        #(#impls)*
-    };
+/// Implement a chainlink from its metadata.
+/// 
+/// This is called by the metadata macro that
+/// [`chainlink!`] generates, and is not meant
+/// to be used directly.
+#[doc(hidden)]
+#[proc_macro]
+pub fn __impl_chainlink(input: TokenStream) -> TokenStream {
+    output(typechain_codegen::expand_impl_chainlink(input.into()))
+}
 
-    expanded.into()
-}
\ No newline at end of file
+/// Implement the chainlinks of a flattened chain.
+/// 
+/// This is called by the metadata macro that
+/// [`chain!`] generates, and is not meant to be
+/// used directly.
+#[doc(hidden)]
+#[proc_macro]
+pub fn __flatten_chain(input: TokenStream) -> TokenStream {
+    output(typechain_codegen::expand_flatten_chain(input.into()))
+}
@@ -2,9 +2,10 @@
 
 //! # `typechain`
 //! 
-//! This crate re-exports the `typechain` macros. In the
-//! future, it may also contain other utilities for
-//! working with `typechain`-generated code.
+//! This crate re-exports the `typechain` macros, along
+//! with the [`Schema`] descriptions they generate for
+//! every chainlink and chain, which can be exported as
+//! JSON Schema or [TypeScript](typescript) declarations.
 //! 
 //! ## Usage
 //! 
@@ -32,6 +33,38 @@
 //! ```
 
 pub use typechain_macros::*;
+pub use schema::{Described, Schema, SchemaKind, SchemaField, SchemaVariant, FieldKind};
+
+pub mod schema;
+pub mod typescript;
+
+/// Attribute forms of the `typechain` macros.
+/// 
+/// ```
+/// use typechain::{attr, chainlink};
+/// 
+/// chainlink!(Foo => {
+///     const foo: u32;
+/// });
+/// 
+/// #[attr::chain]
+/// #[derive(Debug, Clone)]
+/// pub struct Bar {
+///     #[link(Foo)]
+///     foo: u32,
+/// }
+/// 
+/// let bar = Bar { foo: 42 };
+/// let foo: &Foo = &bar;
+/// ```
+pub mod attr {
+    pub use typechain_macros::chain_attr as chain;
+    pub use typechain_macros::chainlink_attr as chainlink;
+}
+
+// Generated code refers to this crate as `::typechain`,
+// including code generated inside this crate.
+extern crate self as typechain;
 
 
 chainlink!(HasTags => {
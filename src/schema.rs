@@ -0,0 +1,480 @@
+//! Descriptions of the data in chainlinks and chains.
+//!
+//! Every chainlink and chain implements [`Described`],
+//! whose `SCHEMA` constant describes its fields and
+//! can be rendered as a
+//! [JSON Schema](https://json-schema.org) with
+//! [`Schema::to_json_schema`].
+//!
+//! ```
+//! use typechain::{chainlink, chain, Described};
+//!
+//! chainlink!(Person => {
+//!     const name: String;
+//!     mut age: u8;
+//! });
+//!
+//! chain!(Employee => {
+//!     @Person
+//!     const name: String;
+//!
+//!     @Person
+//!     mut age: u8;
+//! });
+//!
+//! assert_eq!(Employee::SCHEMA.chainlinks, &["Person"]);
+//!
+//! // Chainlinks given as definitions are referenced
+//! // by the chains implementing them.
+//! let json = Employee::SCHEMA.to_json_schema_with(&[Person::SCHEMA]);
+//! assert!(json.contains("\"$ref\": \"#/$defs/Person\""));
+//! ```
+
+use std::fmt::Write;
+
+
+/// A chainlink or chain with a [`Schema`].
+///
+/// This is a trait rather than an inherent constant,
+/// so it does not collide with items of the same name
+/// on the chain.
+pub trait Described {
+    /// The description of the type.
+    const SCHEMA: Schema;
+}
+
+/// A description of a chainlink or chain.
+///
+/// More details may be described in later versions,
+/// so schemas are only built by the macros.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct Schema {
+    /// The name of the chainlink or chain.
+    pub name: &'static str,
+    /// What the schema describes.
+    pub kind: SchemaKind,
+    /// The type parameters, without lifetimes or
+    /// const parameters.
+    pub generics: &'static [&'static str],
+    /// The fields, in the order they are declared.
+    /// This is empty for enum chains.
+    pub fields: &'static [SchemaField],
+    /// The variants of an enum chain.
+    pub variants: &'static [SchemaVariant],
+    /// The chainlinks a chain implements, as written
+    /// in the chain. This is empty for chainlinks.
+    pub chainlinks: &'static [&'static str]
+}
+
+/// What a [`Schema`] describes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SchemaKind {
+    /// A chainlink.
+    Chainlink,
+    /// A chain struct.
+    Chain,
+    /// A chain enum.
+    Enum
+}
+
+/// A field of a chainlink or chain.
+///
+/// Like [`Schema`], this is only built by the macros.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct SchemaField {
+    /// The name of the field. Tuple fields are named
+    /// by their index.
+    pub name: &'static str,
+    /// How the field is declared.
+    pub kind: FieldKind,
+    /// The Rust type of the field.
+    pub ty: &'static str
+}
+
+/// How a field is declared.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FieldKind {
+    /// A `const` field.
+    Const,
+    /// A `mut` field.
+    Mut,
+    /// A `static` item, which is not stored in the
+    /// value.
+    Static,
+    /// A field that chainlinks are delegated to.
+    Delegate,
+    /// A flattened chain.
+    Flatten
+}
+
+/// A variant of an enum chain.
+///
+/// Like [`Schema`], this is only built by the macros.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct SchemaVariant {
+    /// The name of the variant.
+    pub name: &'static str,
+    /// The fields of the variant.
+    pub fields: &'static [SchemaField]
+}
+
+impl SchemaField {
+    /// Create a field. This is used by the macros,
+    /// and is not part of the public API.
+    #[doc(hidden)]
+    pub const fn __new(name: &'static str, kind: FieldKind, ty: &'static str) -> Self {
+        Self { name, kind, ty }
+    }
+}
+
+impl SchemaVariant {
+    /// Create a variant. This is used by the macros,
+    /// and is not part of the public API.
+    #[doc(hidden)]
+    pub const fn __new(name: &'static str, fields: &'static [SchemaField]) -> Self {
+        Self { name, fields }
+    }
+}
+
+/// A JSON value, built while rendering a schema.
+enum Json {
+    Bool(bool),
+    Number(u64),
+    String(String),
+    Array(Vec<Json>),
+    Object(Vec<(&'static str, Json)>)
+}
+
+impl Json {
+    /// Create an object from its entries.
+    fn object(entries: impl IntoIterator<Item = (&'static str, Json)>) -> Self {
+        Json::Object(entries.into_iter().collect())
+    }
+
+    /// Create a `{ "type": ... }` object.
+    fn ty(ty: &str) -> Self {
+        Json::object([("type", Json::String(ty.to_string()))])
+    }
+
+    /// Write the value with two-space indentation.
+    fn write(&self, out: &mut String, indent: usize) {
+        match self {
+            Json::Bool(value) => write!(out, "{}", value).unwrap(),
+            Json::Number(value) => write!(out, "{}", value).unwrap(),
+            Json::String(value) => write_string(out, value),
+            Json::Array(items) if items.is_empty() => out.push_str("[]"),
+            Json::Array(items) => {
+                out.push('[');
+
+                for (i, item) in items.iter().enumerate() {
+                    out.push_str(if i == 0 { "\n" } else { ",\n" });
+                    out.push_str(&"  ".repeat(indent + 1));
+                    item.write(out, indent + 1);
+                }
+
+                write!(out, "\n{}]", "  ".repeat(indent)).unwrap();
+            },
+            Json::Object(entries) if entries.is_empty() => out.push_str("{}"),
+            Json::Object(entries) => {
+                out.push('{');
+
+                for (i, (key, value)) in entries.iter().enumerate() {
+                    out.push_str(if i == 0 { "\n" } else { ",\n" });
+                    out.push_str(&"  ".repeat(indent + 1));
+                    write_string(out, key);
+                    out.push_str(": ");
+                    value.write(out, indent + 1);
+                }
+
+                write!(out, "\n{}}}", "  ".repeat(indent)).unwrap();
+            }
+        }
+    }
+}
+
+/// Write a JSON string literal.
+fn write_string(out: &mut String, value: &str) {
+    out.push('"');
+
+    for c in value.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            c if c.is_control() => write!(out, "\\u{:04x}", c as u32).unwrap(),
+            c => out.push(c)
+        }
+    }
+
+    out.push('"');
+}
+
+/// Split a type into its name, without modules, and
+/// its generic arguments.
+pub(crate) fn split_type(ty: &str) -> (&str, Vec<&str>) {
+    let (path, args) = match ty.find('<') {
+        Some(start) if ty.ends_with('>') => (&ty[..start], &ty[start + 1..ty.len() - 1]),
+        _ => (ty, "")
+    };
+
+    let name = path.rsplit("::").next().unwrap_or(path).trim();
+
+    (name, split_args(args))
+}
+
+/// Split a list of types at commas outside of brackets.
+pub(crate) fn split_args(args: &str) -> Vec<&str> {
+    let mut parts = Vec::new();
+    let mut depth = 0;
+    let mut start = 0;
+
+    for (i, c) in args.char_indices() {
+        match c {
+            '<' | '(' | '[' => depth += 1,
+            '>' | ')' | ']' => depth -= 1,
+            ',' if depth == 0 => {
+                parts.push(args[start..i].trim());
+                start = i + 1;
+            },
+            _ => {}
+        }
+    }
+
+    parts.push(args[start..].trim());
+    parts.retain(|p| !p.is_empty());
+
+    parts
+}
+
+/// Remove references and their lifetimes from a type.
+pub(crate) fn strip_reference(mut ty: &str) -> &str {
+    while let Some(rest) = ty.strip_prefix('&') {
+        ty = rest.trim_start();
+
+        if ty.starts_with('\'') {
+            ty = ty.split_once(char::is_whitespace).map_or("", |(_, rest)| rest).trim_start();
+        }
+
+        ty = ty.strip_prefix("mut ").unwrap_or(ty).trim_start();
+    }
+
+    ty
+}
+
+impl Schema {
+    /// Create a schema. This is used by the macros,
+    /// and is not part of the public API.
+    #[doc(hidden)]
+    pub const fn __new(
+        name: &'static str,
+        kind: SchemaKind,
+        generics: &'static [&'static str],
+        fields: &'static [SchemaField],
+        variants: &'static [SchemaVariant],
+        chainlinks: &'static [&'static str]
+    ) -> Self {
+        Self { name, kind, generics, fields, variants, chainlinks }
+    }
+
+    /// Render the schema as a JSON Schema document.
+    ///
+    /// Fields of types the renderer does not know
+    /// accept any value.
+    pub fn to_json_schema(&self) -> String {
+        self.to_json_schema_with(&[])
+    }
+
+    /// Render the schema as a JSON Schema document,
+    /// with other schemas as definitions.
+    ///
+    /// Fields whose type is one of the definitions
+    /// reference it, and implemented chainlinks that
+    /// are definitions are required with `allOf` when
+    /// the chain stores their items in its own fields.
+    pub fn to_json_schema_with(&self, defs: &[Schema]) -> String {
+        let mut root = vec![
+            ("$schema", Json::String("https://json-schema.org/draft/2020-12/schema".to_string()))
+        ];
+
+        if let Json::Object(entries) = self.json(defs) {
+            root.extend(entries);
+        }
+
+        if !defs.is_empty() {
+            root.push(("$defs", Json::object(defs.iter().map(|def| (def.name, def.json(defs))))));
+        }
+
+        let mut out = String::new();
+        Json::Object(root).write(&mut out, 0);
+        out.push('\n');
+
+        out
+    }
+
+    /// Build the JSON Schema of this schema.
+    fn json(&self, defs: &[Schema]) -> Json {
+        let mut entries = vec![("title", Json::String(self.name.to_string()))];
+
+        if self.kind == SchemaKind::Enum {
+            // Variants are externally tagged, as in
+            // `{ "Variant": { ...fields } }`.
+            let variants = self.variants.iter().map(|variant| {
+                Json::object([
+                    ("type", Json::String("object".to_string())),
+                    ("properties", Json::object([(variant.name, fields_json(variant.fields, true, defs))])),
+                    ("required", Json::Array(vec![Json::String(variant.name.to_string())])),
+                    ("additionalProperties", Json::Bool(false))
+                ])
+            }).collect();
+
+            entries.push(("oneOf", Json::Array(variants)));
+        } else if let Json::Object(fields) = fields_json(self.fields, self.kind == SchemaKind::Chain, defs) {
+            entries.extend(fields);
+        }
+
+        // Chains only meet the schema of a chainlink if
+        // they store its items under the same names, not
+        // through delegates, tuple fields or variants.
+        let parents = self.chainlinks.iter().filter_map(|parent| {
+            let (name, _) = split_type(parent);
+
+            defs.iter().find(|d| d.name == name)
+        }).filter(|def| self.kind == SchemaKind::Chainlink || self.stores_items(def, defs)).map(|def| def_ref(def.name)).collect::<Vec<_>>();
+
+        if !parents.is_empty() {
+            entries.push(("allOf", Json::Array(parents)));
+        }
+
+        Json::Object(entries)
+    }
+
+    /// Check whether this schema has a same-named
+    /// `const` or `mut` field for every item of
+    /// `parent` that is stored in values, including
+    /// the items of the chainlinks `parent` extends
+    /// that are in `schemas`.
+    pub(crate) fn stores_items(&self, parent: &Schema, schemas: &[Schema]) -> bool {
+        if self.kind == SchemaKind::Enum {
+            return false;
+        }
+
+        let stored = parent.fields.iter().filter(|f| f.kind != FieldKind::Static).all(|item| {
+            self.fields.iter().any(|f| f.name == item.name && matches!(f.kind, FieldKind::Const | FieldKind::Mut))
+        });
+
+        stored && parent.chainlinks.iter().all(|grandparent| {
+            let (name, _) = split_type(grandparent);
+
+            schemas.iter().find(|s| s.name == name).is_none_or(|s| self.stores_items(s, schemas))
+        })
+    }
+}
+
+/// Build a `$ref` to a definition.
+fn def_ref(name: &str) -> Json {
+    Json::object([("$ref", Json::String(format!("#/$defs/{}", name)))])
+}
+
+/// Build the object schema of a list of fields.
+///
+/// `static` items are not stored in values, so they
+/// are left out. Fields are required unless they are
+/// an `Option`. Chainlinks are implemented by chains
+/// with more fields, so only chains are `closed` to
+/// other properties.
+fn fields_json(fields: &[SchemaField], closed: bool, defs: &[Schema]) -> Json {
+    let fields = fields.iter().filter(|f| f.kind != FieldKind::Static);
+
+    let properties = fields.clone().map(|f| (f.name, type_json(f.ty, defs))).collect::<Vec<_>>();
+    let required = fields
+        .filter(|f| split_type(strip_reference(f.ty)).0 != "Option")
+        .map(|f| Json::String(f.name.to_string()))
+        .collect();
+
+    let mut entries = vec![
+        ("type", Json::String("object".to_string())),
+        ("properties", Json::Object(properties)),
+        ("required", Json::Array(required))
+    ];
+
+    if closed {
+        entries.push(("additionalProperties", Json::Bool(false)));
+    }
+
+    Json::Object(entries)
+}
+
+/// Build the schema of a Rust type.
+fn type_json(ty: &str, defs: &[Schema]) -> Json {
+    let ty = strip_reference(ty.trim());
+
+    if let Some(inner) = ty.strip_prefix('[').and_then(|t| t.strip_suffix(']')) {
+        let item = split_args(inner).into_iter().next().unwrap_or("");
+        let item = item.split(';').next().unwrap_or(item).trim();
+
+        return Json::object([
+            ("type", Json::String("array".to_string())),
+            ("items", type_json(item, defs))
+        ]);
+    }
+
+    if let Some(inner) = ty.strip_prefix('(').and_then(|t| t.strip_suffix(')')) {
+        let items = split_args(inner);
+
+        if items.is_empty() {
+            return Json::ty("null");
+        }
+
+        let count = items.len() as u64;
+
+        return Json::object([
+            ("type", Json::String("array".to_string())),
+            ("prefixItems", Json::Array(items.into_iter().map(|t| type_json(t, defs)).collect())),
+            ("minItems", Json::Number(count)),
+            ("maxItems", Json::Number(count))
+        ]);
+    }
+
+    let (name, args) = split_type(ty);
+    let arg = |i: usize| type_json(args.get(i).copied().unwrap_or(""), defs);
+
+    match name {
+        "bool" => Json::ty("boolean"),
+        "u8" | "u16" | "u32" | "u64" | "u128" | "usize" => Json::object([
+            ("type", Json::String("integer".to_string())),
+            ("minimum", Json::Number(0))
+        ]),
+        "i8" | "i16" | "i32" | "i64" | "i128" | "isize" => Json::ty("integer"),
+        "f32" | "f64" => Json::ty("number"),
+        "str" | "String" => Json::ty("string"),
+        "char" => Json::object([
+            ("type", Json::String("string".to_string())),
+            ("minLength", Json::Number(1)),
+            ("maxLength", Json::Number(1))
+        ]),
+        "Option" => Json::object([
+            ("anyOf", Json::Array(vec![arg(0), Json::ty("null")]))
+        ]),
+        "Box" | "Rc" | "Arc" | "Cow" => arg(args.len().saturating_sub(1)),
+        "Vec" | "VecDeque" | "LinkedList" => Json::object([
+            ("type", Json::String("array".to_string())),
+            ("items", arg(0))
+        ]),
+        "HashSet" | "BTreeSet" => Json::object([
+            ("type", Json::String("array".to_string())),
+            ("items", arg(0)),
+            ("uniqueItems", Json::Bool(true))
+        ]),
+        "HashMap" | "BTreeMap" => Json::object([
+            ("type", Json::String("object".to_string())),
+            ("additionalProperties", arg(1))
+        ]),
+        name => match defs.iter().find(|d| d.name == name) {
+            Some(def) => def_ref(def.name),
+            None => Json::object([])
+        }
+    }
+}
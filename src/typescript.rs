@@ -0,0 +1,189 @@
+//! TypeScript declarations for chainlinks and chains.
+//!
+//! Chainlinks become interfaces, with `const` fields
+//! as `readonly` properties, and chains become
+//! interfaces extending the chainlinks they
+//! implement. Enum chains become unions of their
+//! variants, tagged the same way as in
+//! [`Schema::to_json_schema`].
+//!
+//! ```
+//! use typechain::{chainlink, chain, typescript, Described};
+//!
+//! chainlink!(Person => {
+//!     const name: String;
+//!     mut age: u8;
+//! });
+//!
+//! chain!(Employee => {
+//!     @Person
+//!     const name: String;
+//!
+//!     @Person
+//!     mut age: u8;
+//! });
+//!
+//! let declarations = typescript::declarations(&[Person::SCHEMA, Employee::SCHEMA]);
+//!
+//! assert!(declarations.contains("export interface Employee extends Person {"));
+//! ```
+
+use crate::schema::{split_args, split_type, strip_reference};
+use crate::{FieldKind, Schema, SchemaField, SchemaKind};
+
+
+/// Render the declarations of a set of schemas as
+/// the contents of a `.d.ts` file.
+///
+/// Types are only referenced by name if they are one
+/// of `schemas` or a type parameter, and are
+/// `unknown` otherwise, so the file always checks.
+pub fn declarations(schemas: &[Schema]) -> String {
+    schemas.iter().map(|schema| declaration(schema, schemas)).collect::<Vec<_>>().join("\n")
+}
+
+impl Schema {
+    /// Render the TypeScript declaration of the schema.
+    ///
+    /// Other chainlinks and chains are not known, so
+    /// the declaration does not extend or reference
+    /// them. Use [`declarations`] to render several
+    /// schemas that refer to each other.
+    pub fn to_typescript(&self) -> String {
+        declaration(self, &[])
+    }
+}
+
+/// Render the declaration of one schema.
+fn declaration(schema: &Schema, known: &[Schema]) -> String {
+    let scope = Scope { schema, known };
+
+    let generics = match schema.generics {
+        [] => String::new(),
+        generics => format!("<{}>", generics.join(", "))
+    };
+
+    if schema.kind == SchemaKind::Enum {
+        let variants = schema.variants.iter().map(|variant| {
+            format!("\n  | {{ {}: {} }}", variant.name, scope.inline_fields(variant.fields))
+        }).collect::<String>();
+
+        return format!("export type {}{} ={};\n", schema.name, generics, variants);
+    }
+
+    // Parents can only be extended once declared, and
+    // by chains storing their items under their names.
+    let parents = schema.chainlinks.iter().filter(|parent| {
+        known.iter().find(|k| k.name == split_type(parent).0).is_some_and(|k| {
+            schema.kind == SchemaKind::Chainlink || schema.stores_items(k, known)
+        })
+    }).map(|parent| scope.ty(parent)).collect::<Vec<_>>();
+
+    let extends = match parents.as_slice() {
+        [] => String::new(),
+        parents => format!(" extends {}", parents.join(", "))
+    };
+
+    let fields = schema.fields.iter().filter_map(|f| scope.field(f)).map(|f| format!("  {};\n", f)).collect::<String>();
+
+    format!("export interface {}{}{} {{\n{}}}\n", schema.name, generics, extends, fields)
+}
+
+/// The types that can be named while rendering a
+/// declaration.
+struct Scope<'a> {
+    schema: &'a Schema,
+    known: &'a [Schema]
+}
+
+impl Scope<'_> {
+    /// Render a property, or nothing for `static`
+    /// items, which are not stored in values.
+    fn field(&self, field: &SchemaField) -> Option<String> {
+        let readonly = match field.kind {
+            FieldKind::Static => return None,
+            FieldKind::Mut => "",
+            FieldKind::Const | FieldKind::Delegate | FieldKind::Flatten => "readonly "
+        };
+
+        let name = match field.name.parse::<usize>() {
+            Ok(_) => format!("\"{}\"", field.name),
+            Err(_) => field.name.to_string()
+        };
+
+        let ty = strip_reference(field.ty);
+
+        // `Option` fields can be left out, as in JSON.
+        Some(match split_type(ty) {
+            ("Option", args) => format!("{}{}?: {} | null", readonly, name, self.ty(args.first().copied().unwrap_or(""))),
+            _ => format!("{}{}: {}", readonly, name, self.ty(ty))
+        })
+    }
+
+    /// Render fields as an inline object type.
+    fn inline_fields(&self, fields: &[SchemaField]) -> String {
+        let fields = fields.iter().filter_map(|f| self.field(f)).collect::<Vec<_>>();
+
+        match fields.as_slice() {
+            [] => String::from("{}"),
+            fields => format!("{{ {} }}", fields.join("; "))
+        }
+    }
+
+    /// Render a Rust type as a TypeScript type.
+    fn ty(&self, ty: &str) -> String {
+        let ty = strip_reference(ty.trim());
+
+        if let Some(inner) = ty.strip_prefix('[').and_then(|t| t.strip_suffix(']')) {
+            let item = inner.split(';').next().unwrap_or(inner);
+
+            return array(self.ty(item));
+        }
+
+        if let Some(inner) = ty.strip_prefix('(').and_then(|t| t.strip_suffix(')')) {
+            return match split_args(inner).as_slice() {
+                [] => String::from("null"),
+                items => format!("[{}]", items.iter().map(|t| self.ty(t)).collect::<Vec<_>>().join(", "))
+            };
+        }
+
+        let (name, args) = split_type(ty);
+        let arg = |i: usize| self.ty(args.get(i).copied().unwrap_or(""));
+
+        match name {
+            "bool" => String::from("boolean"),
+            "u8" | "u16" | "u32" | "u64" | "u128" | "usize" |
+            "i8" | "i16" | "i32" | "i64" | "i128" | "isize" |
+            "f32" | "f64" => String::from("number"),
+            "str" | "String" | "char" => String::from("string"),
+            "Option" => format!("{} | null", arg(0)),
+            "Box" | "Rc" | "Arc" | "Cow" => arg(args.len().saturating_sub(1)),
+            "Vec" | "VecDeque" | "LinkedList" | "HashSet" | "BTreeSet" => array(arg(0)),
+            "HashMap" | "BTreeMap" => format!("Record<string, {}>", arg(1)),
+            name if self.schema.generics.contains(&name) => name.to_string(),
+            name => match self.known.iter().find(|k| k.name == name) {
+                Some(_) => {
+                    // Lifetimes are not generic parameters
+                    // in TypeScript.
+                    let args = args.iter().filter(|a| !a.starts_with('\'')).map(|a| self.ty(a)).collect::<Vec<_>>();
+
+                    match args.as_slice() {
+                        [] => name.to_string(),
+                        args => format!("{}<{}>", name, args.join(", "))
+                    }
+                },
+                None => String::from("unknown")
+            }
+        }
+    }
+}
+
+/// Render an array type, wrapping unions in
+/// parentheses.
+fn array(item: String) -> String {
+    if item.contains(" | ") {
+        format!("({})[]", item)
+    } else {
+        format!("{}[]", item)
+    }
+}
@@ -0,0 +1,159 @@
+use typechain::{attr, chainlink, use_chains};
+use_chains![typechain::HasTags];
+
+
+chainlink!(Person => {
+    const name: String;
+    const age: u8;
+});
+
+chainlink!(Adult => {
+    const job: String;
+});
+
+chainlink!(State<T> => {
+    mut value: T;
+});
+
+#[attr::chain]
+#[derive(Debug, Clone, PartialEq)]
+#[link(@HasTags static tags: Vec<&'static str> = vec!["parent"])]
+pub struct Parent {
+    #[link(Person)]
+    name: String,
+
+    #[link(Person)]
+    age: u8,
+
+    #[link(Adult)]
+    job: String,
+
+    pub children: Vec<String>
+}
+
+#[attr::chain]
+pub struct Counter<T: Copy> where T: Default {
+    #[link(mut State<T>)]
+    value: T
+}
+
+#[attr::chain]
+pub struct Manager {
+    #[flatten]
+    base: Parent,
+
+    #[link(delegate State<u32>)]
+    reports: Counter<u32>
+}
+
+#[test]
+fn test_attributes() {
+    let parent = Parent {
+        name: "Dave".to_string(),
+        age: 40,
+        job: "Software Engineer".to_string(),
+        children: vec!["John".to_string()]
+    };
+
+    assert_eq!(parent.clone(), parent);
+    assert_eq!(parent.name(), "Dave");
+    assert_eq!(parent.tags(), vec!["parent"]);
+    assert_eq!(parent.children.len(), 1);
+
+    let mut manager = Manager {
+        base: parent,
+        reports: Counter { value: 0 }
+    };
+
+    *manager.value() += 2;
+
+    let adults: Vec<&Adult> = vec![&manager, &manager.base];
+
+    assert!(adults.iter().all(|a| a.job() == "Software Engineer"));
+    assert_eq!(manager.age(), &40);
+    assert_eq!(manager.reports.value, 2);
+}
+
+
+#[attr::chainlink(pointers)]
+/// Something with an identifier.
+pub trait Identified: std::fmt::Debug {
+    #[field]
+    fn id(&self) -> &u64;
+
+    #[field]
+    fn visits(&mut self) -> &mut u32;
+
+    #[field]
+    fn kind(&self) -> &'static str;
+
+    fn describe(&self) -> String {
+        format!("{} {}", self.kind(), self.id())
+    }
+}
+
+#[attr::chainlink]
+pub trait Keyed {
+    type Key: Copy + PartialEq + std::fmt::Debug;
+
+    fn key(&self) -> Self::Key;
+}
+
+#[attr::chainlink]
+pub trait Labeled<T: Clone> where T: Default {
+    #[field]
+    fn label(&self) -> &T;
+}
+
+#[attr::chain]
+#[derive(Debug)]
+#[link(@Identified static kind: &'static str = "user")]
+pub struct User {
+    #[link(Identified)]
+    id: u64,
+
+    #[link(mut Identified)]
+    visits: u32,
+
+    #[link(Labeled<String>)]
+    label: String
+}
+
+impl KeyedChainlink for User {
+    type Key = u64;
+
+    fn key(&self) -> u64 {
+        self.id
+    }
+}
+
+#[attr::chain]
+#[derive(Debug)]
+pub struct Admin {
+    #[link(delegate Identified, Keyed)]
+    user: User
+}
+
+fn visit<I: IdentifiedChainlink>(mut item: I) -> u32 {
+    *item.visits() += 1;
+    *item.visits()
+}
+
+#[test]
+fn test_trait_attributes() {
+    let mut admin = Admin {
+        user: User { id: 7, visits: 0, label: "Dave".to_string() }
+    };
+
+    assert_eq!(admin.id(), &7);
+    assert_eq!(admin.describe(), "user 7");
+    assert_eq!(visit(&mut admin), 1);
+    assert_eq!(visit(Box::new(&mut admin)), 2);
+
+    let objects: Vec<&Identified> = vec![&admin, &admin.user];
+    let keys: Vec<&Keyed<u64>> = vec![&admin, &admin.user];
+
+    assert!(objects.iter().all(|o| o.kind() == "user"));
+    assert!(keys.iter().all(|k| k.key() == 7));
+    assert_eq!(admin.user.label(), "Dave");
+}
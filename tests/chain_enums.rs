@@ -0,0 +1,99 @@
+use typechain::{chainlink, chain};
+
+
+chainlink!(Animal => {
+    const legs: u8;
+    static sound: &'static str;
+});
+
+chainlink!(Named => {
+    mut name: String;
+});
+
+chainlink!(Described => {
+    const description: String;
+});
+
+chain!(enum Pet => {
+    Dog {
+        @Animal
+        const legs: u8;
+
+        @Animal
+        static sound: &'static str = "woof";
+
+        @Named
+        mut name: String;
+
+        @Described
+        const description: String;
+    },
+    Fish {
+        @Animal
+        static legs: u8 = 0;
+
+        @Animal
+        static sound: &'static str = "blub";
+
+        @Named
+        mut name: String;
+
+        // Not a constant expression, so it is only
+        // evaluated when first borrowed.
+        @Described
+        static description: String = format!("a fish with {} legs", 0);
+    }
+});
+
+// Every variant provides `legs` with a static, but
+// it is still a `const` item of the chainlink.
+chain!(enum Legless => {
+    Snake {
+        @Animal
+        static legs: u8 = 0;
+
+        @Animal
+        static sound: &'static str = "hiss";
+    },
+    Worm {
+        @Animal
+        static legs: u8 = 0;
+
+        @Animal
+        static sound: &'static str = "...";
+    }
+});
+
+#[test]
+fn test_chain_enum() {
+    let mut pets = [
+        Pet::Dog { legs: 4, name: "Rex".to_string(), description: "a good dog".to_string() },
+        Pet::Fish { name: "Nemo".to_string() }
+    ];
+
+    for pet in pets.iter_mut() {
+        pet.name().push('!');
+    }
+
+    let animals: Vec<&Animal> = pets.iter().map(|p| p as &Animal).collect();
+
+    assert_eq!(animals.iter().map(|a| *a.legs()).collect::<Vec<_>>(), vec![4, 0]);
+    assert_eq!(animals.iter().map(|a| a.sound()).collect::<Vec<_>>(), vec!["woof", "blub"]);
+
+    assert_eq!(pets[0].description(), "a good dog");
+    assert_eq!(pets[1].description(), "a fish with 0 legs");
+    assert!(std::ptr::eq(pets[1].description(), pets[1].description()));
+
+    match &pets[1] {
+        Pet::Fish { name } => assert_eq!(name, "Nemo!"),
+        Pet::Dog { .. } => unreachable!()
+    }
+}
+
+#[test]
+fn test_static_chain_enum() {
+    let animals: [&Animal; 2] = [&Legless::Snake {}, &Legless::Worm {}];
+
+    assert_eq!(animals.iter().map(|a| *a.legs()).collect::<Vec<_>>(), vec![0, 0]);
+    assert_eq!(animals[0].sound(), "hiss");
+}
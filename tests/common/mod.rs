@@ -0,0 +1,62 @@
+//! Chainlinks and chains shared by the schema and
+//! TypeScript tests.
+
+// Only the schemas are used, so the fields are never read.
+#![allow(dead_code)]
+
+use std::collections::HashMap;
+
+use typechain::{chainlink, chain};
+
+
+chainlink!(Person => {
+    const name: String;
+    mut age: u8;
+    static species: &'static str;
+});
+
+chainlink!(Scored => {
+    const scores: HashMap<String, f64>;
+    const nickname: Option<String>;
+});
+
+chainlink!(State<T> => {
+    mut value: T;
+});
+
+chain!(Employee => {
+    @Person
+    const name: String;
+
+    @Person
+    mut age: u8;
+
+    @Person
+    static species: &'static str = "human";
+
+    @Scored
+    const scores: HashMap<String, f64>;
+
+    @Scored
+    const nickname: Option<String>;
+});
+
+chain!(Manager => {
+    @Person
+    delegate employee: Employee;
+
+    const reports: u8;
+});
+
+chain!(Pair(@State<u32> mut u32, pub const String));
+
+chain!(enum Rank => {
+    Junior {
+        @State<u8>
+        mut value: u8;
+    },
+    Senior {
+        @State<u8>
+        mut value: u8;
+    }
+});
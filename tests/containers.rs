@@ -6,30 +6,37 @@ chainlink!(Container<T> => {
     fn set(&mut self, value: T);
 });
 
-impl_chains!(Box<T> => {
+impl_chains!(impl<T> Box<T> where T: Clone + Copy => {
     fn get(&self) -> T {
         **self
     } in Container<T>;
 
+    #[allow(clippy::replace_box)]
     fn set(&mut self, value: T) {
         *self = Box::new(value);
     } in Container<T>;
-} where <T: Clone + Copy>);
+});
 
-impl_chains!(Vec<T> => {
-    fn get(&self) -> T {
-        self[0]
-    } in Container<T>;
+// The deprecated `where <...>` spelling is still accepted.
+#[allow(deprecated)]
+mod sugar {
+    use super::*;
 
-    fn set(&mut self, value: T) {
-        if self.is_empty() {
-            self.push(value);
-            return;
-        }
+    impl_chains!(Vec<T> => {
+        fn get(&self) -> T {
+            self[0]
+        } in Container<T>;
 
-        self[0] = value;
-    } in Container<T>;
-} where <T: Clone + Copy>);
+        fn set(&mut self, value: T) {
+            if self.is_empty() {
+                self.push(value);
+                return;
+            }
+
+            self[0] = value;
+        } in Container<T>;
+    } where <T: Clone + Copy>);
+}
 
 #[test]
 fn test_box() {
@@ -45,4 +52,29 @@ fn test_box() {
 
     assert_eq!(my_box, Box::new(1));
     assert_eq!(my_vec, vec![1]);
-}
\ No newline at end of file
+}
+
+pub struct Counter(u64);
+
+chainlink!(Total => {
+    fn total(&self) -> u64;
+});
+
+impl_chains!(impl<T> Vec<T> where T: Into<u64> + Copy, for<'a> &'a T: PartialEq => {
+    fn total(&self) -> u64 {
+        self.iter().map(|v| (*v).into()).sum()
+    } in Total;
+});
+
+impl_chains!(Counter => {
+    fn total(&self) -> u64 {
+        self.0
+    } in Total;
+});
+
+#[test]
+fn test_where_clause() {
+    let totals: Vec<Box<Total>> = vec![Box::new(vec![1u32, 2, 3]), Box::new(Counter(4))];
+
+    assert_eq!(totals.iter().map(|t| t.total()).sum::<u64>(), 10);
+}
@@ -0,0 +1,75 @@
+use typechain::{chainlink, chain, impl_chains};
+
+
+chainlink!(Person => {
+    const name: String;
+    mut age: u8;
+});
+
+chainlink!(Greeter => {
+    fn greet(&self, greeting: &str) -> String;
+});
+
+chain!(Parent => {
+    @Person
+    const name: String;
+
+    @Person
+    mut age: u8;
+});
+
+impl GreeterChainlink for Parent {
+    fn greet(&self, greeting: &str) -> String {
+        format!("{}, {}!", greeting, self.name)
+    }
+}
+
+chain!(Employee => {
+    @Person
+    @Greeter
+    delegate person: Parent;
+
+    pub const salary: u32;
+});
+
+pub struct Contractor {
+    inner: Parent,
+    pub rate: u32
+}
+
+impl_chains!(Contractor => {
+    delegate inner: Parent in Person;
+    delegate inner: Parent in Greeter;
+});
+
+#[test]
+fn test_delegate() {
+    let mut employee = Employee {
+        person: Parent {
+            name: "Bob".to_string(),
+            age: 45
+        },
+        salary: 50000
+    };
+
+    *employee.age() += 1;
+
+    assert_eq!(employee.name(), "Bob");
+    assert_eq!(employee.age(), &46);
+    assert_eq!(employee.greet("Hello"), "Hello, Bob!");
+    assert_eq!(employee.salary, 50000);
+
+    let contractor = Contractor {
+        inner: Parent {
+            name: "Alice".to_string(),
+            age: 30
+        },
+        rate: 100
+    };
+
+    let people: Vec<&Person> = vec![&employee, &contractor];
+
+    assert_eq!(people.iter().map(|p| p.name().as_str()).collect::<Vec<_>>(), vec!["Bob", "Alice"]);
+    assert_eq!((&contractor as &Greeter).greet("Hi"), "Hi, Alice!");
+    assert_eq!(contractor.rate, 100);
+}
@@ -0,0 +1,76 @@
+use typechain::{chainlink, chain, impl_chains};
+
+
+chainlink!(Container<T> => {
+    fn get(&self) -> T;
+    fn set(&mut self, value: T);
+});
+
+impl_chains!(impl<T: Copy> Vec<T> => {
+    fn get(&self) -> T {
+        self[0]
+    } in Container<T>;
+
+    fn set(&mut self, value: T) {
+        if self.is_empty() {
+            self.push(value);
+            return;
+        }
+
+        self[0] = value;
+    } in Container<T>;
+});
+
+pub struct Wrapped(Vec<u32>);
+
+impl_chains!(Wrapped => deref self.0 for Container<u32>);
+
+chainlink!(Counter => {
+    const name: String;
+    mut count: u32;
+    static kind: &'static str;
+});
+
+chain!(Inner => {
+    @Counter
+    const name: String;
+
+    @Counter
+    mut count: u32;
+
+    @Counter
+    static kind: &'static str = "inner";
+});
+
+pub struct Outer {
+    inner: Box<Inner>
+}
+
+impl_chains!(Outer => deref *self.inner for Counter);
+
+#[test]
+fn test_deref() {
+    let mut wrapped = Wrapped(vec![]);
+
+    wrapped.set(3);
+
+    assert_eq!(wrapped.get(), 3);
+    assert_eq!(wrapped.0, vec![3]);
+
+    let mut outer = Outer {
+        inner: Box::new(Inner {
+            name: "clicks".to_string(),
+            count: 0
+        })
+    };
+
+    *outer.count() += 2;
+
+    assert_eq!(outer.name(), "clicks");
+    assert_eq!(outer.count(), &2);
+    assert_eq!(outer.kind(), "inner");
+
+    let counters: Vec<&mut Counter> = vec![&mut outer];
+
+    assert_eq!(counters.len(), 1);
+}
@@ -0,0 +1,75 @@
+use typechain::{chainlink, use_chains, Chain};
+use_chains![typechain::HasTags];
+
+
+chainlink!(Post => {
+    const title: String;
+    mut views: u32;
+});
+
+chainlink!(Authored => {
+    const author: String;
+});
+
+#[derive(Debug, Clone, PartialEq, Chain)]
+#[chain(static HasTags = vec!["post"])]
+pub struct Article {
+    #[chain(Post)]
+    title: String,
+
+    #[chain(mut Post)]
+    views: u32,
+
+    #[chain(Authored)]
+    author: String,
+
+    pub body: String
+}
+
+#[derive(Debug, Chain)]
+pub struct Feature {
+    #[chain(flatten)]
+    article: Article,
+
+    pub rank: u8
+}
+
+#[derive(Debug, Chain)]
+pub struct Reprint<'a> {
+    #[chain(delegate Authored)]
+    original: Article,
+
+    pub note: &'a str
+}
+
+#[test]
+fn test_derive() {
+    let mut article = Article {
+        title: "Chains".to_string(),
+        views: 0,
+        author: "Dave".to_string(),
+        body: String::new()
+    };
+
+    *article.views() += 1;
+
+    assert_eq!(article.clone(), article);
+    assert_eq!(article.tags(), vec!["post"]);
+    assert!(article.body.is_empty());
+
+    let reprint = Reprint { original: article.clone(), note: "" };
+
+    assert_eq!(reprint.author(), "Dave");
+    assert!(reprint.note.is_empty());
+
+    let mut feature = Feature { article: article.clone(), rank: 1 };
+
+    *feature.views() += 1;
+
+    let posts: Vec<&Post> = vec![&feature, &article];
+
+    assert_eq!(posts.iter().map(|p| p.title().as_str()).collect::<Vec<_>>(), ["Chains", "Chains"]);
+    assert_eq!(feature.article.views, 2);
+    assert_eq!(feature.rank, 1);
+    assert_eq!(feature.tags(), vec!["post"]);
+}
@@ -0,0 +1,72 @@
+use typechain::{chainlink, chain, chain_enum};
+
+
+chainlink!(Person => {
+    const name: String;
+    mut age: u8;
+});
+
+chainlink!(Greeter => {
+    fn greet(&self, greeting: &str) -> String;
+});
+
+chain!(Parent => {
+    @Person
+    const name: String;
+
+    @Person
+    mut age: u8;
+});
+
+chain!(Child => {
+    @Person
+    const name: String;
+
+    @Person
+    mut age: u8;
+});
+
+impl GreeterChainlink for Parent {
+    fn greet(&self, greeting: &str) -> String {
+        format!("{}, {}!", greeting, self.name)
+    }
+}
+
+impl GreeterChainlink for Child {
+    fn greet(&self, greeting: &str) -> String {
+        format!("{}, little {}!", greeting, self.name)
+    }
+}
+
+chain_enum!(AnyPerson = Parent | Child : Person);
+
+chain_enum!(AnyGreeter = Parent | Child : Greeter);
+
+#[test]
+fn test_chain_enum() {
+    let mut people: Vec<AnyPerson> = vec![
+        Parent { name: "Dave".to_string(), age: 40 }.into(),
+        Child { name: "John".to_string(), age: 10 }.into()
+    ];
+
+    for person in people.iter_mut() {
+        *person.age() += 1;
+    }
+
+    assert_eq!(people.iter().map(|p| p.name().as_str()).collect::<Vec<_>>(), vec!["Dave", "John"]);
+
+    let ages = people.iter_mut().map(|p| *p.as_dyn_mut().age()).collect::<Vec<_>>();
+    assert_eq!(ages, vec![41, 11]);
+
+    let dyns: Vec<&Person> = people.iter().map(|p| p.as_dyn()).collect();
+    assert_eq!(dyns[0].name(), "Dave");
+
+    assert!(matches!(people[0], AnyPerson::Parent(_)));
+
+    let greeters: Vec<AnyGreeter> = vec![
+        Parent { name: "Alice".to_string(), age: 38 }.into(),
+        Child { name: "Jane".to_string(), age: 8 }.into()
+    ];
+
+    assert_eq!(greeters.iter().map(|g| g.greet("Hi")).collect::<Vec<_>>(), vec!["Hi, Alice!", "Hi, little Jane!"]);
+}
@@ -0,0 +1,51 @@
+use typechain::{chain, chainlink, impl_chains};
+
+
+type Age = u8;
+
+chainlink!(Person => {
+    const name: String;
+    const age: u8;
+    static kind: &'static str;
+});
+
+chain!(Parent => {
+    @Person
+    const name: std::string::String;
+
+    @Person
+    const age: Age;
+
+    @Person
+    static kind: &'static str = "parent";
+});
+
+pub struct Foreign {
+    pub name: String,
+    pub age: u8
+}
+
+impl_chains!(Foreign => {
+    const name: String = self.name in Person;
+    const age: Age = self.age in Person;
+    static kind: &str = "foreign" in Person;
+});
+
+#[test]
+fn test_field_types() {
+    let parent = Parent {
+        name: "Dave".to_string(),
+        age: 40
+    };
+
+    let foreign = Foreign {
+        name: "Alice".to_string(),
+        age: 30
+    };
+
+    let people: Vec<&Person> = vec![&parent, &foreign];
+
+    assert_eq!(people.iter().map(|p| *p.age()).sum::<u8>(), 70);
+    assert_eq!(people.iter().map(|p| p.kind()).collect::<Vec<_>>(), ["parent", "foreign"]);
+    assert_eq!(people[0].name(), "Dave");
+}
@@ -0,0 +1,94 @@
+use std::rc::Rc;
+
+use typechain::{chainlink, chain};
+
+
+chainlink!(Person => {
+    const name: String;
+    const age: u8;
+});
+
+chainlink!(Adult => {
+    const job: String;
+});
+
+chain!(Employer => {
+    @Person
+    const name: String;
+
+    @Person
+    const age: u8;
+
+    @Adult
+    const job: String;
+});
+
+chain!(Manager => {
+    #[flatten]
+    base: Employer;
+
+    const reports: Vec<Rc<Person>>;
+});
+
+chain!(Director => {
+    #[flatten]
+    manager: Manager;
+
+    const budget: u32;
+});
+
+chainlink!(RefBox<'a, T> => {
+    const value: &'a T;
+});
+
+chain!(Basic<'a, T> => {
+    @RefBox<'a, T>
+    const value: &'a T;
+});
+
+chain!(Labeled<'a> => {
+    #[flatten]
+    inner: Basic<'a, u32>;
+
+    const label: String;
+});
+
+fn employer(name: &str) -> Employer {
+    Employer {
+        name: name.to_string(),
+        age: 40,
+        job: "Software Engineer".to_string()
+    }
+}
+
+#[test]
+fn test_flatten() {
+    let manager = Manager {
+        base: employer("Dave"),
+        reports: vec![Rc::new(employer("Bob")), Rc::new(employer("Alice"))]
+    };
+
+    assert_eq!(manager.name(), "Dave");
+    assert_eq!(manager.job(), "Software Engineer");
+    assert_eq!(manager.reports.len(), 2);
+
+    let director = Director {
+        manager,
+        budget: 1000
+    };
+
+    let adults: Vec<&Adult> = vec![&director, &director.manager];
+
+    assert!(adults.iter().all(|a| a.job() == "Software Engineer"));
+    assert_eq!(director.age(), &40);
+    assert_eq!(director.budget, 1000);
+
+    let value = 5;
+    let labeled = Labeled {
+        inner: Basic { value: &value },
+        label: "five".to_string()
+    };
+
+    assert_eq!(**labeled.value(), 5);
+    assert_eq!(labeled.label, "five");
+}
@@ -0,0 +1,37 @@
+use typechain::{chainlink, impl_chains};
+
+
+chainlink!(Named => {
+    const name: String;
+    fn renamed(&self, name: &str) -> Self where Self: Sized;
+    fn visit<F: Fn(&str)>(&self, f: F) where Self: Sized;
+});
+
+#[derive(Debug, PartialEq)]
+pub struct Tag {
+    pub label: String
+}
+
+impl_chains!(Tag => {
+    const name = self.label in Named;
+
+    fn renamed(&self, name: &str) -> Self where Self: Sized {
+        Tag { label: name.to_string() }
+    } in Named;
+
+    fn visit<F: Fn(&str)>(&self, f: F) where Self: Sized {
+        f(&self.label)
+    } in Named;
+});
+
+#[test]
+fn test_object_safety() {
+    let tag = Tag { label: "old".to_string() };
+    let renamed = tag.renamed("new");
+
+    renamed.visit(|name| assert_eq!(name, "new"));
+
+    let named: Vec<&Named> = vec![&tag, &renamed];
+
+    assert_eq!(named.iter().map(|n| n.name().as_str()).collect::<Vec<_>>(), ["old", "new"]);
+}
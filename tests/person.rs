@@ -87,6 +87,9 @@ fn test_person() {
         children: vec![parent1.clone(), parent2.clone()]
     };
 
+    assert_eq!(gparent.children.len(), 2);
+    assert_eq!(child.school, "Elementary");
+
     let people: Vec<Rc<Person>> = vec![Rc::new(gparent), Rc::new(employer), child, child2, parent1, parent2];
 
     assert_eq!(people.iter().filter(|p| p.name() == "John").count(), 1);
@@ -0,0 +1,84 @@
+use std::{rc::Rc, sync::Arc};
+
+use typechain::{chainlink, chain};
+
+
+chainlink!(#[pointers] Person => {
+    const name: String;
+    static species: &'static str;
+});
+
+chainlink!(#[pointers] Greeter => {
+    fn greet(&self, greeting: &str) -> String;
+});
+
+chainlink!(#[pointers] State<T> => {
+    mut value: T;
+});
+
+chain!(Child => {
+    @Person
+    const name: String;
+
+    @Person
+    static species: &'static str = "human";
+});
+
+impl GreeterChainlink for Child {
+    fn greet(&self, greeting: &str) -> String {
+        format!("{}, {}!", greeting, self.name)
+    }
+}
+
+impl Child {
+    fn new(name: &str) -> Self {
+        Self {
+            name: name.to_string()
+        }
+    }
+}
+
+chain!(Counter<T> => {
+    @State<T>
+    mut value: T;
+});
+
+fn describe<P: PersonChainlink>(person: P) -> String {
+    format!("{} ({})", person.name(), person.species())
+}
+
+fn welcome<G: GreeterChainlink>(greeter: G) -> String {
+    greeter.greet("Welcome")
+}
+
+fn bump<S: StateChainlink<u32>>(mut state: S) {
+    *state.value() += 1;
+}
+
+#[test]
+fn test_pointers() {
+    let child = Child::new("John");
+
+    assert_eq!(describe(&child), "John (human)");
+    assert_eq!(describe(Box::new(Child::new("Jane"))), "Jane (human)");
+    assert_eq!(describe(Rc::new(Child::new("Dave"))), "Dave (human)");
+    assert_eq!(describe(Arc::new(Child::new("Alice"))), "Alice (human)");
+
+    assert_eq!(welcome(Rc::new(Child::new("Carl"))), "Welcome, Carl!");
+
+    let boxed: Box<Person> = Box::new(Child::new("Bob"));
+    assert_eq!(describe(boxed), "Bob (human)");
+
+    let shared: Rc<Person> = Rc::new(Child::new("Eve"));
+    assert_eq!(describe(&shared), "Eve (human)");
+
+    let mut counter = Counter { value: 0u32 };
+
+    bump(&mut counter);
+    bump(&mut &mut counter);
+
+    let mut boxed: Box<State<u32>> = Box::new(counter);
+    bump(&mut boxed);
+
+    assert_eq!(boxed.value(), &mut 3);
+}
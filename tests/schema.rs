@@ -0,0 +1,249 @@
+use serde_json::{json, Value};
+use typechain::{chain, Described, FieldKind, Schema, SchemaField, SchemaKind};
+
+mod common;
+
+use common::{Person, Scored, State, Employee, Manager, Pair, Rank};
+
+chain!(Versioned;);
+
+// Schemas are trait constants, so chains can still
+// have inherent items of the same name.
+impl Versioned {
+    pub const SCHEMA: u32 = 2;
+}
+
+/// List the name, kind and type of each field.
+fn fields(fields: &[SchemaField]) -> Vec<(&str, FieldKind, &str)> {
+    fields.iter().map(|f| (f.name, f.kind, f.ty)).collect()
+}
+
+#[test]
+fn test_chainlink_schema() {
+    assert_eq!(Person::SCHEMA.name, "Person");
+    assert_eq!(Person::SCHEMA.kind, SchemaKind::Chainlink);
+    assert_eq!(fields(Person::SCHEMA.fields), [
+        ("name", FieldKind::Const, "String"),
+        ("age", FieldKind::Mut, "u8"),
+        ("species", FieldKind::Static, "&'static str")
+    ]);
+
+    assert_eq!(Person::SCHEMA.generics, &[] as &[&str]);
+    assert_eq!(State::<u32>::SCHEMA.generics, &["T"]);
+    assert_eq!(fields(State::<u32>::SCHEMA.fields), [("value", FieldKind::Mut, "T")]);
+}
+
+#[test]
+fn test_chain_schema() {
+    assert_eq!(Employee::SCHEMA.kind, SchemaKind::Chain);
+    assert_eq!(Employee::SCHEMA.chainlinks, &["Person", "Scored"]);
+    assert_eq!(fields(Employee::SCHEMA.fields)[3], ("scores", FieldKind::Const, "HashMap<String, f64>"));
+
+    assert_eq!(Pair::SCHEMA.chainlinks, &["State<u32>"]);
+    assert_eq!(fields(Pair::SCHEMA.fields), [
+        ("0", FieldKind::Mut, "u32"),
+        ("1", FieldKind::Const, "String")
+    ]);
+
+    assert_eq!(Rank::SCHEMA.kind, SchemaKind::Enum);
+    assert_eq!(Rank::SCHEMA.variants.iter().map(|v| v.name).collect::<Vec<_>>(), ["Junior", "Senior"]);
+    assert_eq!(fields(Rank::SCHEMA.variants[0].fields), [("value", FieldKind::Mut, "u8")]);
+}
+
+#[test]
+fn test_inherent_schema() {
+    assert_eq!(Versioned::SCHEMA, 2);
+    assert_eq!(<Versioned as Described>::SCHEMA.name, "Versioned");
+}
+
+#[test]
+fn test_json_schema() {
+    let defs: [Schema; 2] = [Person::SCHEMA, Scored::SCHEMA];
+
+    assert_eq!(Employee::SCHEMA.to_json_schema_with(&defs), r##"{
+  "$schema": "https://json-schema.org/draft/2020-12/schema",
+  "title": "Employee",
+  "type": "object",
+  "properties": {
+    "name": {
+      "type": "string"
+    },
+    "age": {
+      "type": "integer",
+      "minimum": 0
+    },
+    "scores": {
+      "type": "object",
+      "additionalProperties": {
+        "type": "number"
+      }
+    },
+    "nickname": {
+      "anyOf": [
+        {
+          "type": "string"
+        },
+        {
+          "type": "null"
+        }
+      ]
+    }
+  },
+  "required": [
+    "name",
+    "age",
+    "scores"
+  ],
+  "additionalProperties": false,
+  "allOf": [
+    {
+      "$ref": "#/$defs/Person"
+    },
+    {
+      "$ref": "#/$defs/Scored"
+    }
+  ],
+  "$defs": {
+    "Person": {
+      "title": "Person",
+      "type": "object",
+      "properties": {
+        "name": {
+          "type": "string"
+        },
+        "age": {
+          "type": "integer",
+          "minimum": 0
+        }
+      },
+      "required": [
+        "name",
+        "age"
+      ]
+    },
+    "Scored": {
+      "title": "Scored",
+      "type": "object",
+      "properties": {
+        "scores": {
+          "type": "object",
+          "additionalProperties": {
+            "type": "number"
+          }
+        },
+        "nickname": {
+          "anyOf": [
+            {
+              "type": "string"
+            },
+            {
+              "type": "null"
+            }
+          ]
+        }
+      },
+      "required": [
+        "scores"
+      ]
+    }
+  }
+}
+"##);
+}
+
+/// Check a value against the parts of JSON Schema
+/// that schemas are rendered with.
+fn validate(schema: &Value, root: &Value, value: &Value) -> bool {
+    if let Some(path) = schema["$ref"].as_str() {
+        let name = path.strip_prefix("#/$defs/").unwrap();
+
+        return validate(&root["$defs"][name], root, value);
+    }
+
+    let all = |key: &str| schema[key].as_array().into_iter().flatten();
+    let number = |key: &str| schema[key].as_f64();
+    let count = |key: &str| schema[key].as_u64().map(|n| n as usize);
+
+    let typed = match schema["type"].as_str() {
+        None => true,
+        Some("object") => value.is_object(),
+        Some("array") => value.is_array(),
+        Some("string") => value.is_string(),
+        Some("integer") => value.is_i64() || value.is_u64(),
+        Some("number") => value.is_number(),
+        Some("boolean") => value.is_boolean(),
+        Some("null") => value.is_null(),
+        Some(other) => panic!("unknown type `{}`", other)
+    };
+
+    let object = value.as_object().is_none_or(|object| {
+        let properties = schema["properties"].as_object();
+
+        all("required").all(|key| object.contains_key(key.as_str().unwrap()))
+            && object.iter().all(|(key, item)| match properties.and_then(|p| p.get(key)) {
+                Some(property) => validate(property, root, item),
+                None => match &schema["additionalProperties"] {
+                    Value::Bool(allowed) => *allowed,
+                    Value::Null => true,
+                    other => validate(other, root, item)
+                }
+            })
+    });
+
+    let array = value.as_array().is_none_or(|array| {
+        let prefix = schema["prefixItems"].as_array().map_or(0, |p| p.len());
+
+        array.iter().zip(all("prefixItems")).all(|(item, schema)| validate(schema, root, item))
+            && array.iter().skip(prefix).all(|item| schema["items"].is_null() || validate(&schema["items"], root, item))
+            && count("minItems").is_none_or(|n| array.len() >= n)
+            && count("maxItems").is_none_or(|n| array.len() <= n)
+    });
+
+    let scalar = value.as_f64().is_none_or(|n| {
+        number("minimum").is_none_or(|min| n >= min) && number("maximum").is_none_or(|max| n <= max)
+    }) && value.as_str().is_none_or(|s| {
+        count("minLength").is_none_or(|n| s.chars().count() >= n) && count("maxLength").is_none_or(|n| s.chars().count() <= n)
+    });
+
+    typed && object && array && scalar
+        && all("allOf").all(|s| validate(s, root, value))
+        && (schema["anyOf"].is_null() || all("anyOf").any(|s| validate(s, root, value)))
+        && (schema["oneOf"].is_null() || all("oneOf").filter(|s| validate(s, root, value)).count() == 1)
+}
+
+/// Check a value against the JSON Schema of a chain.
+fn matches(schema: Schema, defs: &[Schema], value: Value) -> bool {
+    let root = serde_json::from_str::<Value>(&schema.to_json_schema_with(defs)).unwrap();
+
+    validate(&root, &root, &value)
+}
+
+#[test]
+fn test_json_schema_values() {
+    let defs = [Person::SCHEMA, Scored::SCHEMA, State::<u8>::SCHEMA];
+
+    let employee = json!({ "name": "Ada", "age": 36, "scores": { "math": 1.0 } });
+
+    assert!(matches(Employee::SCHEMA, &defs, employee.clone()));
+    assert!(!matches(Employee::SCHEMA, &defs, json!({ "name": "Ada", "age": 36 })));
+    assert!(!matches(Employee::SCHEMA, &defs, json!({ "name": "Ada", "age": -1, "scores": {} })));
+
+    // Delegated and tuple chains do not store the
+    // items of their chainlinks under their names.
+    assert!(matches(Manager::SCHEMA, &defs, json!({ "employee": employee, "reports": 2 })));
+    assert!(matches(Pair::SCHEMA, &defs, json!({ "0": 1, "1": "one" })));
+    assert!(!matches(Pair::SCHEMA, &defs, json!({ "0": 1, "1": "one", "value": 1 })));
+
+    assert!(matches(Rank::SCHEMA, &defs, json!({ "Junior": { "value": 1 } })));
+    assert!(!matches(Rank::SCHEMA, &defs, json!({ "Junior": {} })));
+    assert!(!matches(Rank::SCHEMA, &defs, json!({ "Junior": { "value": 1 }, "Senior": { "value": 2 } })));
+}
+
+#[test]
+fn test_json_schema_enum() {
+    let json = Rank::SCHEMA.to_json_schema();
+
+    assert!(json.contains("\"oneOf\""));
+    assert!(json.contains("\"Junior\": {"));
+    assert!(json.contains("\"required\": [\n            \"value\"\n          ]"));
+}
@@ -0,0 +1,61 @@
+use typechain::{chainlink, chain};
+
+
+chainlink!(sealed Shape = Circle | square::Square | triangle::Triangle => {
+    const name: String;
+});
+
+chain!(Circle => {
+    @Shape
+    const name: String;
+
+    const radius: f64;
+});
+
+mod square {
+    use typechain::{chain, use_chains};
+
+    use_chains![super::Shape];
+
+    chain!(Square => {
+        @Shape
+        const name: String;
+
+        pub const side: f64;
+    });
+
+    impl Square {
+        pub fn new(side: f64) -> Self {
+            Self { name: "square".to_string(), side }
+        }
+    }
+}
+
+#[path = "sealed/triangle.rs"]
+mod triangle;
+
+use square::Square;
+use triangle::Triangle;
+
+fn area(shape: &Shape) -> f64 {
+    match shape.kind() {
+        ShapeKind::Circle(circle) => std::f64::consts::PI * circle.radius * circle.radius,
+        ShapeKind::Square(square) => square.side * square.side,
+        ShapeKind::Triangle(triangle) => triangle.base * triangle.height / 2.0
+    }
+}
+
+#[test]
+fn test_sealed() {
+    let shapes: Vec<Box<Shape>> = vec![
+        Box::new(Circle { name: "circle".to_string(), radius: 1.0 }),
+        Box::new(Square::new(2.0)),
+        Box::new(Triangle::new(3.0, 2.0))
+    ];
+
+    let areas = shapes.iter().map(|s| area(s.as_ref())).collect::<Vec<_>>();
+
+    assert_eq!(areas, vec![std::f64::consts::PI, 4.0, 3.0]);
+    assert_eq!(shapes[1].name(), "square");
+    assert!(matches!(Circle { name: "c".to_string(), radius: 0.5 }.kind(), ShapeKind::Circle(_)));
+}
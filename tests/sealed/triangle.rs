@@ -0,0 +1,16 @@
+use typechain::chain;
+
+
+chain!(Triangle => {
+    @super::Shape
+    const name: String;
+
+    pub const base: f64;
+    pub const height: f64;
+});
+
+impl Triangle {
+    pub fn new(base: f64, height: f64) -> Self {
+        Self { name: "triangle".to_string(), base, height }
+    }
+}
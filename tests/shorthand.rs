@@ -0,0 +1,41 @@
+use typechain::{chainlink, impl_chains};
+
+
+chainlink!(Person => {
+    const name: String;
+    mut age: u8;
+    static species: &'static str;
+    fn greet(&self) -> String;
+});
+
+pub struct ForeignUser {
+    pub username: String,
+    pub age: u8
+}
+
+impl_chains!(ForeignUser => {
+    const name = self.username in Person;
+    mut age = self.age in Person;
+    static species = "human" in Person;
+
+    fn greet(&self) -> String {
+        format!("Hello, {}!", self.username)
+    } in Person;
+});
+
+#[test]
+fn test_shorthand() {
+    let mut user = ForeignUser {
+        username: "alice".to_string(),
+        age: 30
+    };
+
+    *user.age() += 1;
+
+    let person: &Person = &user;
+
+    assert_eq!(person.name(), "alice");
+    assert_eq!(person.species(), "human");
+    assert_eq!(person.greet(), "Hello, alice!");
+    assert_eq!(user.age, 31);
+}
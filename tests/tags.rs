@@ -38,6 +38,10 @@ fn test_tags() {
         comments,
     };
 
+    assert_eq!(post.title, "Hello, world!");
+    assert_eq!(post.comments.len(), 3);
+    assert!(post.comments.iter().all(|c| c.body.starts_with("Goodbye")));
+
     let has_tags: &HasTags = &post;
 
     assert_eq!(has_tags.tags(), vec!["post"]);
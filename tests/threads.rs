@@ -0,0 +1,70 @@
+use std::{sync::Arc, thread};
+
+use typechain::{chainlink, chain};
+
+
+chainlink!(#[pointers] Person: Send + Sync => {
+    const name: String;
+});
+
+chainlink!(#[pointers] Animal => {
+    const legs: u8;
+});
+
+chain!(Child => {
+    @Person
+    const name: String;
+});
+
+chain!(Dog => {
+    @Animal
+    const legs: u8;
+});
+
+#[test]
+fn test_send_sync_supertraits() {
+    let people: Vec<Box<Person>> = vec![Box::new(Child { name: "John".to_string() })];
+
+    let names = thread::spawn(move || {
+        people.iter().map(|p| p.name().clone()).collect::<Vec<_>>()
+    }).join().unwrap();
+
+    assert_eq!(names, vec!["John"]);
+}
+
+#[test]
+fn test_companion_aliases() {
+    let animals: Vec<Box<SendAnimal>> = vec![Box::new(Dog { legs: 4 })];
+
+    let legs = thread::spawn(move || {
+        animals.iter().map(|a| *a.legs()).sum::<u8>()
+    }).join().unwrap();
+
+    assert_eq!(legs, 4);
+
+    let shared: Arc<SyncAnimal> = Arc::new(Dog { legs: 3 });
+    let other = shared.clone();
+
+    assert_eq!(thread::spawn(move || *other.legs()).join().unwrap(), 3);
+    assert_eq!(shared.legs(), &3);
+}
+
+mod imports {
+    use typechain::use_chains;
+
+    // Types named like the aliases are not shadowed.
+    pub struct SendAnimal;
+
+    use_chains![super::Animal];
+
+    pub fn legs(animal: &Animal) -> u8 {
+        *animal.legs()
+    }
+}
+
+#[test]
+fn test_use_chains_aliases() {
+    let _ = imports::SendAnimal;
+
+    assert_eq!(imports::legs(&Dog { legs: 2 }), 2);
+}
@@ -0,0 +1,52 @@
+use typechain::{chainlink, chain, use_chains};
+use_chains![typechain::HasTags];
+
+
+chainlink!(Identified => {
+    const id: u64;
+    static kind: &'static str;
+});
+
+chainlink!(Counter => {
+    mut count: u32;
+});
+
+chainlink!(Named => {
+    const first: String;
+    const last: String;
+});
+
+chain!(UserId(@Identified const u64); @Identified static kind: &'static str = "user");
+
+chain!(Hits(@Counter mut u32, const String));
+
+chain!(FullName(@Named const std::string::String, @Named const String));
+
+chain!(Anonymous;);
+
+chain!(Tagged; @HasTags static tags: Vec<&'static str> = vec!["tagged"]);
+
+#[test]
+fn test_tuple_chains() {
+    let user = UserId(42);
+    let identified: &Identified = &user;
+
+    assert_eq!(identified.id(), &42);
+    assert_eq!(identified.kind(), "user");
+    assert_eq!(user.0, 42);
+
+    let mut hits = Hits(0, "page".to_string());
+    *hits.count() += 3;
+
+    assert_eq!(hits.count(), &3);
+    assert_eq!(hits.1, "page");
+
+    let name = FullName("Ada".to_string(), "Lovelace".to_string());
+
+    assert_eq!((name.first().as_str(), name.last().as_str()), ("Ada", "Lovelace"));
+
+    let _ = Anonymous;
+
+    let tagged: &HasTags = &Tagged;
+    assert_eq!(tagged.tags(), vec!["tagged"]);
+}
@@ -0,0 +1,72 @@
+use typechain::{chain, typescript, Described};
+
+mod common;
+
+use common::{Person, Scored, State, Employee, Manager, Pair, Rank};
+
+
+pub struct Opaque;
+
+// Arrays of types TypeScript cannot name.
+chain!(Handles(pub const Vec<Opaque>));
+
+#[test]
+fn test_declarations() {
+    let declarations = typescript::declarations(&[
+        Person::SCHEMA,
+        Scored::SCHEMA,
+        State::<u32>::SCHEMA,
+        Employee::SCHEMA,
+        Manager::SCHEMA,
+        Pair::SCHEMA,
+        Rank::SCHEMA,
+        Handles::SCHEMA
+    ]);
+
+    assert_eq!(declarations, r#"export interface Person {
+  readonly name: string;
+  age: number;
+}
+
+export interface Scored {
+  readonly scores: Record<string, number>;
+  readonly nickname?: string | null;
+}
+
+export interface State<T> {
+  value: T;
+}
+
+export interface Employee extends Person, Scored {
+  readonly name: string;
+  age: number;
+  readonly scores: Record<string, number>;
+  readonly nickname?: string | null;
+}
+
+export interface Manager {
+  readonly employee: Employee;
+  readonly reports: number;
+}
+
+export interface Pair {
+  "0": number;
+  readonly "1": string;
+}
+
+export type Rank =
+  | { Junior: { value: number } }
+  | { Senior: { value: number } };
+
+export interface Handles {
+  readonly "0": unknown[];
+}
+"#);
+}
+
+#[test]
+fn test_to_typescript() {
+    // Parents are only extended when they are declared.
+    assert_eq!(Employee::SCHEMA.to_typescript().lines().next(), Some("export interface Employee {"));
+    assert_eq!(Pair::SCHEMA.to_typescript().lines().next(), Some("export interface Pair {"));
+}
@@ -0,0 +1,7 @@
+#[test]
+fn test_ui() {
+    let cases = trybuild::TestCases::new();
+
+    cases.compile_fail("tests/ui/*.rs");
+    cases.pass("tests/ui/pass/*.rs");
+}
@@ -0,0 +1,9 @@
+use typechain::attr;
+
+
+#[attr::chain(Person)]
+pub struct Parent {
+    name: String
+}
+
+fn main() {}
@@ -0,0 +1,19 @@
+use typechain::{attr, chainlink};
+
+
+chainlink!(Person => {
+    const name: String;
+});
+
+chainlink!(Named => {
+    mut name: String;
+});
+
+#[attr::chain]
+pub struct Parent {
+    #[link(Person)]
+    #[link(mut Named)]
+    name: String
+}
+
+fn main() {}
@@ -0,0 +1,14 @@
+use typechain::{attr, chainlink};
+
+
+chainlink!(Counter => {
+    mut count: u32;
+});
+
+#[attr::chain]
+pub struct Count {
+    #[link(mut Counter)]
+    pub count: u32
+}
+
+fn main() {}
@@ -0,0 +1,14 @@
+use typechain::{attr, chainlink};
+
+
+chainlink!(Person => {
+    const name: String;
+});
+
+#[attr::chain]
+#[link(@Person const name: String)]
+pub struct Parent {
+    name: String
+}
+
+fn main() {}
@@ -0,0 +1,7 @@
+use typechain::attr;
+
+
+#[attr::chain]
+pub struct UserId(u64);
+
+fn main() {}
@@ -0,0 +1,10 @@
+use typechain::attr;
+
+
+#[attr::chainlink(boxed)]
+pub trait Person {
+    #[field]
+    fn name(&self) -> &String;
+}
+
+fn main() {}
@@ -0,0 +1,10 @@
+use typechain::attr;
+
+
+#[attr::chainlink]
+pub trait Sized<const N: usize> {
+    #[field]
+    fn items(&self) -> &[u8; N];
+}
+
+fn main() {}
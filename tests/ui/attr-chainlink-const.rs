@@ -0,0 +1,9 @@
+use typechain::attr;
+
+
+#[attr::chainlink]
+pub trait Person {
+    const LEGS: u8;
+}
+
+fn main() {}
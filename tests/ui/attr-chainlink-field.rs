@@ -0,0 +1,10 @@
+use typechain::attr;
+
+
+#[attr::chainlink]
+pub trait Person {
+    #[field]
+    fn name(&self, formal: bool) -> &String;
+}
+
+fn main() {}
@@ -0,0 +1,11 @@
+use typechain::attr;
+
+
+#[attr::chainlink]
+pub trait Lender {
+    type Loan<'a> where Self: 'a;
+
+    fn lend(&self) -> u8;
+}
+
+fn main() {}
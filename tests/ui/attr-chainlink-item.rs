@@ -0,0 +1,9 @@
+use typechain::attr;
+
+
+#[attr::chainlink]
+pub trait Person {
+    typechain::use_chains![];
+}
+
+fn main() {}
@@ -0,0 +1,21 @@
+use typechain::{chain, chainlink};
+
+
+chainlink!(Person => {
+    const name: String;
+});
+
+chain!(Inner => {
+    @Person
+    const name: String;
+});
+
+chain!(Outer => {
+    @Person
+    delegate inner: Inner;
+
+    @Person
+    const name: String;
+});
+
+fn main() {}
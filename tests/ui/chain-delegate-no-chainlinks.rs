@@ -0,0 +1,17 @@
+use typechain::{chain, chainlink};
+
+
+chainlink!(Person => {
+    const name: String;
+});
+
+chain!(Parent => {
+    @Person
+    const name: String;
+});
+
+chain!(Employee => {
+    delegate person: Parent;
+});
+
+fn main() {}
@@ -0,0 +1,32 @@
+use typechain::{chain, chain_enum, chainlink, impl_chains};
+
+
+chainlink!(Named => {
+    const name: String;
+    fn renamed(&self, name: &str) -> Self where Self: Sized;
+});
+
+pub struct Tag {
+    pub label: String
+}
+
+impl_chains!(Tag => {
+    const name = self.label in Named;
+
+    fn renamed(&self, name: &str) -> Self where Self: Sized {
+        Tag { label: name.to_string() }
+    } in Named;
+});
+
+chain!(Label => {
+    @Named
+    delegate tag: Tag;
+});
+
+pub struct Sticker(Tag);
+
+impl_chains!(Sticker => deref self.0 for Named);
+
+chain_enum!(AnyNamed = Tag : Named);
+
+fn main() {}
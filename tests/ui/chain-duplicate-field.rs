@@ -0,0 +1,9 @@
+use typechain::chain;
+
+
+chain!(Parent => {
+    const name: String;
+    const name: u8;
+});
+
+fn main() {}
@@ -0,0 +1,16 @@
+use typechain::{chain, chainlink};
+
+
+chainlink!(Person => {
+    static kind: &'static str;
+});
+
+chain!(Parent => {
+    @Person
+    static kind: &'static str = "a";
+
+    @Person
+    static kind: &'static str = "b";
+});
+
+fn main() {}
@@ -0,0 +1,17 @@
+use typechain::{chain, chainlink};
+
+
+chainlink!(Animal => {
+    const legs: u8;
+});
+
+chain!(Dog => {
+    @Animal
+    const legs: u8;
+});
+
+chain!(enum Pet => {
+    Pup { @Animal delegate dog: Dog }
+});
+
+fn main() {}
@@ -0,0 +1,33 @@
+use typechain::{chain, chainlink};
+
+
+chainlink!(Animal => {
+    const legs: u8;
+    mut name: String;
+    static sound: &'static str;
+});
+
+chain!(enum Pet => {
+    Dog {
+        @Animal
+        const legs: u16;
+
+        @Animal
+        mut name: String;
+
+        @Animal
+        static sound: &'static str = "woof";
+    },
+    Fish {
+        @Animal
+        static legs: u32 = 0;
+
+        @Animal
+        mut name: &'static str;
+
+        @Animal
+        static sound: String = String::new();
+    }
+});
+
+fn main() {}
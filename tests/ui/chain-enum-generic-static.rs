@@ -0,0 +1,19 @@
+use typechain::{chain, chainlink};
+
+
+chainlink!(Holder<T> => {
+    const value: T;
+});
+
+chain!(enum Slot<T> => {
+    Full {
+        @Holder<T>
+        const value: T;
+    },
+    Empty {
+        @Holder<T>
+        static value: T = Default::default();
+    }
+});
+
+fn main() {}
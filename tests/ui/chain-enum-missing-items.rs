@@ -0,0 +1,28 @@
+use typechain::{chain, chainlink};
+
+
+chainlink!(Animal => {
+    const legs: u8;
+    mut name: String;
+    static sound: &'static str;
+    fn describe(&self) -> String;
+});
+
+chain!(enum Pet => {
+    Dog {
+        @Animal
+        const legs: u8;
+
+        @Animal
+        mut name: String;
+
+        @Animal
+        static sound: &'static str = "woof";
+    },
+    Fish {
+        @Animal
+        static legs: u8 = 0;
+    }
+});
+
+fn main() {}
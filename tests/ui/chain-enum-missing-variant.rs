@@ -0,0 +1,13 @@
+use typechain::{chain, chainlink};
+
+
+chainlink!(Animal => {
+    const legs: u8;
+});
+
+chain!(enum Pet => {
+    Dog { @Animal const legs: u8 },
+    Fish { const fins: u8 }
+});
+
+fn main() {}
@@ -0,0 +1,13 @@
+use typechain::{chain, chainlink};
+
+
+chainlink!(Animal => {
+    mut legs: u8;
+});
+
+chain!(enum Pet => {
+    Dog { @Animal mut legs: u8 },
+    Fish { @Animal const legs: u8 }
+});
+
+fn main() {}
@@ -0,0 +1,12 @@
+use typechain::{chain, chainlink};
+
+
+chainlink!(Animal => {
+    const legs: u8;
+});
+
+chain!(enum Pet => {
+    Dog { @Animal pub const legs: u8 }
+});
+
+fn main() {}
@@ -0,0 +1,9 @@
+use typechain::chain;
+
+
+chain!(Parent => {
+    #[serde(skip)]
+    const name: String;
+});
+
+fn main() {}
@@ -0,0 +1,21 @@
+use typechain::{chain, chainlink};
+
+
+chainlink!(Person => {
+    const age: u8;
+    mut score: u32;
+    static kind: &'static str;
+});
+
+chain!(Parent => {
+    @Person
+    const age: u16;
+
+    @Person
+    mut score: u64;
+
+    @Person
+    static kind: String = String::new();
+});
+
+fn main() {}
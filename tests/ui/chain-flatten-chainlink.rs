@@ -0,0 +1,13 @@
+use typechain::{chain, chainlink};
+
+
+chainlink!(Person => {
+    const name: String;
+});
+
+chain!(Parent => {
+    #[flatten]
+    person: Person;
+});
+
+fn main() {}
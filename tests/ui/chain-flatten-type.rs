@@ -0,0 +1,9 @@
+use typechain::chain;
+
+
+chain!(Parent => {
+    #[flatten]
+    inner: &'static str;
+});
+
+fn main() {}
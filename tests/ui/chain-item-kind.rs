@@ -0,0 +1,13 @@
+use typechain::{chain, chainlink};
+
+
+chainlink!(Person => {
+    const name: String;
+});
+
+chain!(Parent => {
+    @Person
+    mut name: String;
+});
+
+fn main() {}
@@ -0,0 +1,14 @@
+use typechain::{chain, chainlink};
+
+
+chainlink!(Person => {
+    const name: String;
+    fn greet(&self) -> String;
+});
+
+chain!(Parent => {
+    @Person
+    const name: String;
+});
+
+fn main() {}
@@ -0,0 +1,16 @@
+use typechain::{chain, chainlink};
+
+
+chainlink!(Person => {
+    const name: String;
+    const age: u8;
+    mut score: u32;
+    static kind: &'static str;
+});
+
+chain!(Parent => {
+    @Person
+    const name: String;
+});
+
+fn main() {}
@@ -0,0 +1,13 @@
+use typechain::{chain, chainlink};
+
+
+chainlink!(Person => {
+    mut age: u8;
+});
+
+chain!(Parent => {
+    @Person
+    pub mut age: u8;
+});
+
+fn main() {}
@@ -0,0 +1,13 @@
+use typechain::{chain, chainlink};
+
+
+chainlink!(Person => {
+    static kind: &'static str;
+});
+
+chain!(Parent => {
+    @Person
+    pub static kind: &'static str = "parent";
+});
+
+fn main() {}
@@ -0,0 +1,11 @@
+use typechain::{chain, chainlink};
+
+
+chainlink!(Pair => {
+    const left: u64;
+    const right: u64;
+});
+
+chain!(Both(@Pair const u64));
+
+fn main() {}
@@ -0,0 +1,6 @@
+use typechain::chain;
+
+
+chain!(UserId(static u64));
+
+fn main() {}
@@ -0,0 +1,10 @@
+use typechain::{chain, chainlink};
+
+
+chainlink!(Identified => {
+    const id: u64;
+});
+
+chain!(UserId(@Identified const u32));
+
+fn main() {}
@@ -0,0 +1,10 @@
+use typechain::{chain, chainlink};
+
+
+chainlink!(Counter => {
+    mut count: u32;
+});
+
+chain!(Count(@Counter pub mut u32));
+
+fn main() {}
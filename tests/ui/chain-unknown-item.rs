@@ -0,0 +1,16 @@
+use typechain::{chain, chainlink};
+
+
+chainlink!(Person => {
+    const name: String;
+});
+
+chain!(Parent => {
+    @Person
+    const name: String;
+
+    @Person
+    const age: u8;
+});
+
+fn main() {}
@@ -0,0 +1,18 @@
+use typechain::{chainlink, chain_enum, impl_chains};
+
+
+chainlink!(Person => {
+    fn create() -> u8 where Self: Sized;
+});
+
+pub struct Parent;
+
+impl_chains!(Parent => {
+    fn create() -> u8 where Self: Sized {
+        0
+    } in Person;
+});
+
+chain_enum!(AnyPerson = Parent : Person);
+
+fn main() {}
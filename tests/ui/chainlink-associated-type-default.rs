@@ -0,0 +1,8 @@
+use typechain::chainlink;
+
+
+chainlink!(Person => {
+    type Id = u32;
+});
+
+fn main() {}
@@ -0,0 +1,10 @@
+use typechain::chainlink;
+
+
+chainlink!(Person => {
+    fn greet(&self) -> String {
+        String::new()
+    }
+});
+
+fn main() {}
@@ -0,0 +1,9 @@
+use typechain::chainlink;
+
+
+chainlink!(Greeter => {
+    #[default]
+    fn greet(&self) -> String;
+});
+
+fn main() {}
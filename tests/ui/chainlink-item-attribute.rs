@@ -0,0 +1,9 @@
+use typechain::chainlink;
+
+
+chainlink!(Person => {
+    #[inline]
+    fn greet(&self) -> String;
+});
+
+fn main() {}
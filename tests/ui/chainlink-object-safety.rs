@@ -0,0 +1,12 @@
+use typechain::chainlink;
+
+
+chainlink!(Person => {
+    fn renamed(&self) -> Self;
+    fn visit<F: Fn(&str)>(&self, f: F);
+    fn new() -> u8;
+    fn shown(&self, f: impl Fn(&str));
+    fn greeting(&self) -> impl std::fmt::Display;
+});
+
+fn main() {}
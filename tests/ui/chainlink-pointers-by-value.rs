@@ -0,0 +1,8 @@
+use typechain::chainlink;
+
+
+chainlink!(#[pointers] Person => {
+    fn into_name(self) -> String;
+});
+
+fn main() {}
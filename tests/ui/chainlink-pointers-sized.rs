@@ -0,0 +1,8 @@
+use typechain::chainlink;
+
+
+chainlink!(#[pointers] Person => {
+    fn renamed(&self) -> Self where Self: Sized;
+});
+
+fn main() {}
@@ -0,0 +1,14 @@
+use typechain::chainlink;
+
+
+mod round {
+    pub struct Circle;
+}
+
+pub struct Circle;
+
+chainlink!(sealed Shape = Circle | round::Circle => {
+    static sides: u32;
+});
+
+fn main() {}
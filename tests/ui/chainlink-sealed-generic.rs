@@ -0,0 +1,8 @@
+use typechain::chainlink;
+
+
+chainlink!(sealed Shape<T> = Circle => {
+    const size: T;
+});
+
+fn main() {}
@@ -0,0 +1,18 @@
+use typechain::{chain, chainlink};
+
+
+chainlink!(sealed Shape = Circle => {
+    static sides: u32;
+});
+
+chain!(Circle => {
+    @Shape
+    static sides: u32 = 0;
+});
+
+chain!(Square => {
+    @Shape
+    static sides: u32 = 4;
+});
+
+fn main() {}
@@ -0,0 +1,8 @@
+use typechain::chainlink;
+
+
+chainlink!(#[pointers] sealed Shape = Circle => {
+    const size: u32;
+});
+
+fn main() {}
@@ -0,0 +1,8 @@
+use typechain::chainlink;
+
+
+chainlink!(Person => {
+    let name: String;
+});
+
+fn main() {}
@@ -0,0 +1,8 @@
+use typechain::chainlink;
+
+
+chainlink!(#[derive(Debug)] Person => {
+    const name: String;
+});
+
+fn main() {}
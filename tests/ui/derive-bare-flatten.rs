@@ -0,0 +1,20 @@
+use typechain::{chainlink, Chain};
+
+
+chainlink!(Post => {
+    const title: String;
+});
+
+#[derive(Chain)]
+pub struct Article {
+    #[chain(Post)]
+    title: String
+}
+
+#[derive(Chain)]
+pub struct Feature {
+    #[flatten]
+    article: Article
+}
+
+fn main() {}
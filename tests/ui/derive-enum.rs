@@ -0,0 +1,6 @@
+#[derive(typechain::Chain)]
+pub enum Pet {
+    Dog
+}
+
+fn main() {}
@@ -0,0 +1,17 @@
+use typechain::{chainlink, Chain};
+
+
+chainlink!(Post => {
+    const title: String;
+    static kind: &'static str;
+});
+
+#[derive(Chain)]
+#[chain(static Post = "article")]
+#[chain(static Post = "essay")]
+pub struct Article {
+    #[chain(Post)]
+    title: String
+}
+
+fn main() {}
@@ -0,0 +1,28 @@
+use typechain::{chainlink, impl_chains};
+
+
+chainlink!(Person => {
+    const name: String;
+});
+
+pub struct Inner {
+    name: String
+}
+
+pub struct Outer {
+    inner: Inner
+}
+
+impl_chains!(Inner => {
+    const name = self.name in Person;
+});
+
+impl_chains!(Outer => {
+    delegate inner: Inner in Person;
+
+    fn name(&self) -> &String {
+        unimplemented!()
+    } in Person;
+});
+
+fn main() {}
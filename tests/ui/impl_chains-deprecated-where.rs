@@ -0,0 +1,16 @@
+#![deny(deprecated)]
+
+use typechain::{chainlink, impl_chains};
+
+
+chainlink!(Container<T> => {
+    fn get(&self) -> T;
+});
+
+impl_chains!(Vec<T> => {
+    fn get(&self) -> T {
+        self[0]
+    } in Container<T>;
+} where <T: Copy>);
+
+fn main() {}
@@ -0,0 +1,23 @@
+use typechain::{attr, impl_chains};
+
+
+#[attr::chainlink]
+pub trait Keyed {
+    type Key;
+
+    fn key(&self) -> u32;
+}
+
+pub struct Wrapped(Vec<u32>);
+
+impl KeyedChainlink for Vec<u32> {
+    type Key = u32;
+
+    fn key(&self) -> u32 {
+        0
+    }
+}
+
+impl_chains!(Wrapped => deref self.0 for Keyed);
+
+fn main() {}
@@ -0,0 +1,16 @@
+use typechain::{chainlink, impl_chains};
+
+
+chainlink!(Person => {
+    const name: String;
+});
+
+pub struct Foreign {
+    pub name: &'static str
+}
+
+impl_chains!(Foreign => {
+    const name: &'static str = self.name in Person;
+});
+
+fn main() {}
@@ -0,0 +1,14 @@
+use typechain::{chainlink, impl_chains};
+
+
+chainlink!(Container<T> => {
+    const value: T;
+});
+
+pub struct Wrapper<T>(T);
+
+impl_chains!(impl<T> Wrapper<T> => {
+    const value = self.0 in Container<T>;
+} where <U>);
+
+fn main() {}
@@ -0,0 +1,16 @@
+use typechain::{chainlink, impl_chains};
+
+
+chainlink!(Person => {
+    const name: String;
+});
+
+pub struct Foreign {
+    pub name: String
+}
+
+impl_chains!(Foreign => {
+    mut name = self.name in Person;
+});
+
+fn main() {}
@@ -0,0 +1,19 @@
+use typechain::{chainlink, impl_chains};
+
+
+chainlink!(Person => {
+    const name: String;
+    mut age: u8;
+    static kind: &'static str;
+    fn greet(&self) -> String;
+});
+
+pub struct Foreign {
+    pub name: String
+}
+
+impl_chains!(Foreign => {
+    const name = self.name in Person;
+});
+
+fn main() {}
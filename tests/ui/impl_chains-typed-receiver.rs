@@ -0,0 +1,23 @@
+use typechain::{chainlink, impl_chains};
+
+
+chainlink!(Person => {
+    fn boxed(self: Box<Self>) -> String;
+});
+
+pub struct Inner;
+pub struct Outer {
+    inner: Inner
+}
+
+impl_chains!(Inner => {
+    fn boxed(self: Box<Self>) -> String {
+        String::new()
+    } in Person;
+});
+
+impl_chains!(Outer => {
+    delegate inner: Inner in Person;
+});
+
+fn main() {}
@@ -0,0 +1,17 @@
+use typechain::{chainlink, impl_chains};
+
+
+chainlink!(Person => {
+    const name: String;
+});
+
+pub struct Foreign {
+    pub name: String
+}
+
+impl_chains!(Foreign => {
+    const name = self.name in Person;
+    const age = 0 in Person;
+});
+
+fn main() {}
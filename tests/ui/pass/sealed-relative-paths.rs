@@ -0,0 +1,61 @@
+mod shapes {
+    use typechain::{chain, use_chains};
+
+    use_chains![link::Shape];
+
+
+    pub mod link {
+        use typechain::{chain, chainlink};
+
+
+        chainlink!(sealed Shape = super::Square | super::round::Circle | self::Point | crate::shapes::round::flat::Line => {
+            static sides: u32;
+        });
+
+        chain!(Point => {
+            @self::Shape
+            static sides: u32 = 0;
+        });
+    }
+
+    chain!(Square => {
+        @self::link::Shape
+        static sides: u32 = 4;
+    });
+
+    pub mod round {
+        use typechain::chain;
+
+
+        chain!(Circle => {
+            @super::link::Shape
+            static sides: u32 = 0;
+        });
+
+        pub mod flat {
+            use typechain::chain;
+
+
+            chain!(Line => {
+                @crate::shapes::link::Shape
+                static sides: u32 = 1;
+            });
+        }
+    }
+
+    pub fn sides(shape: &Shape) -> u32 {
+        match shape.kind() {
+            link::ShapeKind::Square(square) => square.sides(),
+            link::ShapeKind::Circle(circle) => circle.sides(),
+            link::ShapeKind::Point(point) => point.sides(),
+            link::ShapeKind::Line(line) => line.sides()
+        }
+    }
+}
+
+fn main() {
+    assert_eq!(shapes::sides(&shapes::Square {}), 4);
+    assert_eq!(shapes::sides(&shapes::round::Circle {}), 0);
+    assert_eq!(shapes::sides(&shapes::link::Point {}), 0);
+    assert_eq!(shapes::sides(&shapes::round::flat::Line {}), 1);
+}